@@ -0,0 +1,76 @@
+//! Compares `linear_aim` against its original iterator-based root selection
+//!
+//! Run with `cargo bench --bench linear_aim`.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use guidance::{linear_aim, Target, Tolerances};
+use rand::{distributions::Uniform, SeedableRng};
+
+const SPEED: f64 = 600.0;
+
+/// `linear_aim` as originally written, selecting the root with iterator adapters
+fn reference(target: &Target<f64>, speed: f64) -> Option<(na::Unit<na::Vector3<f64>>, f64)> {
+    let a = target.velocity.norm_squared() - (speed * speed);
+    if a.abs() < 1e-3 {
+        return Some((na::Unit::new_normalize(target.position), 0.0));
+    }
+    let b = 2. * target.position.dot(&target.velocity);
+    let c = target.position.norm_squared();
+    let rt = Some(b * b - 4. * a * c).filter(|&d| d >= 0.0)?.sqrt();
+    let t0 = (-b + rt) / (2. * a);
+    let t1 = (-b - rt) / (2. * a);
+    let t = [t0, t1]
+        .iter()
+        .cloned()
+        .filter(|&x| x >= 0.0)
+        .min_by(|x, y| x.partial_cmp(y).unwrap())?;
+    let pos = target.position + target.velocity * t;
+    Some((na::Unit::new_normalize(pos), t))
+}
+
+/// Whether `reference` and `linear_aim` take the same branch, i.e. the input is clear of the
+/// degenerate and rounding cases where `linear_aim`'s relative tolerances differ from the original
+/// absolute ones
+fn same_branch(target: &Target<f64>, speed: f64) -> bool {
+    let tolerances = Tolerances::<f64>::default();
+    let (target_speed_squared, speed_squared) = (target.velocity.norm_squared(), speed * speed);
+    let a = target_speed_squared - speed_squared;
+    let b = 2. * target.position.dot(&target.velocity);
+    let four_ac = 4. * a * target.position.norm_squared();
+    a.abs() >= 1e-3
+        && a.abs() > tolerances.degeneracy * (target_speed_squared + speed_squared)
+        && (b * b - four_ac).abs() > tolerances.discriminant * (b * b + four_ac.abs())
+}
+
+fn bench(c: &mut Criterion) {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    let range = Uniform::new(-1e3, 1e3);
+    let targets = (0..10_000)
+        .map(|_| Target {
+            position: na::Vector3::from_distribution(&range, &mut rng),
+            velocity: na::Vector3::from_distribution(&range, &mut rng),
+        })
+        .filter(|target| same_branch(target, SPEED))
+        .collect::<Vec<_>>();
+    let mut group = c.benchmark_group("linear_aim");
+    group.bench_function("iterator", |b| {
+        b.iter(|| {
+            for target in &targets {
+                black_box(reference(black_box(target), black_box(SPEED)));
+            }
+        })
+    });
+    group.bench_function("branching", |b| {
+        b.iter(|| {
+            for target in &targets {
+                black_box(linear_aim(black_box(target), black_box(SPEED)));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
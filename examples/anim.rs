@@ -147,18 +147,10 @@ impl Sim {
         const MAX_BOOST: f64 = 1e3;
         const BOOST_ACCEL: f64 = MAX_BOOST / BOOST_TIME;
 
-        let target = Target {
-            position: self.target.position - self.missile.position,
-            velocity: self.target.velocity - self.missile.velocity,
-        };
+        let target = Target::relative(&self.missile.position, &self.missile.velocity, &self.target.position, &self.target.velocity);
         let steering = if target.is_closing() {
             let a = guidance::linear_steer(&target, &self.missile.velocity, self.missile.velocity.norm()).unwrap().0 / TIMESTEP;
-            let ratio = self.max_steering_accel / a.norm();
-            if ratio < 1.0 {
-                a * ratio
-            } else {
-                a
-            }
+            guidance::clamp_acceleration(a, self.max_steering_accel)
         } else {
             na::zero()
         };
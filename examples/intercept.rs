@@ -0,0 +1,60 @@
+//! Searches for, acquires, homes on, and detonates against a crossing target
+
+use guidance::{
+    step_engagement, Acquisition, AcquisitionState, Body, EngagementState, Ipn, ProximityFuze,
+    SearchAndHome, WeaveSearch,
+};
+
+const TIMESTEP: f64 = 1e-3;
+const TIME_LIMIT: f64 = 60.0;
+
+fn main() {
+    let mut state = EngagementState::new(
+        Body {
+            position: na::Point3::origin(),
+            velocity: na::Vector3::new(0.0, 600.0, 0.0),
+        },
+        Body {
+            position: na::Point3::new(3000.0, 14000.0, 500.0),
+            velocity: na::Vector3::new(-200.0, 0.0, 0.0),
+        },
+        300.0,
+    );
+    let mut law = SearchAndHome {
+        acquisition: Acquisition::new(10_000.0, 0.6, 0.5),
+        search: WeaveSearch::new(50.0, 4.0, na::Vector3::z_axis()),
+        law: Ipn {
+            navigation_constant: 4.0,
+        },
+    };
+    let fuze = ProximityFuze { radius: 5.0 };
+    let mut acquired = None;
+    let mut closest = state.range();
+    while state.time < TIME_LIMIT {
+        if let Some(miss) = fuze.check(&state.relative(), TIMESTEP) {
+            println!(
+                "detonated at t = {:.3}s, missing by {:.2}m",
+                state.time, miss
+            );
+            return;
+        }
+        step_engagement(&mut state, &mut law, TIMESTEP);
+        closest = closest.min(state.range());
+        let locked = law.acquisition.state() == AcquisitionState::Locked;
+        if locked && acquired.is_none() {
+            acquired = Some(state.time);
+            println!(
+                "locked at t = {:.3}s, range {:.0}m",
+                state.time,
+                state.range()
+            );
+        } else if !locked && acquired.is_some() {
+            acquired = None;
+            println!("lost lock at t = {:.3}s", state.time);
+        }
+    }
+    match acquired {
+        Some(_) => println!("missed; closest approach {:.2}m", closest),
+        None => println!("never acquired; closest approach {:.2}m", closest),
+    }
+}
@@ -0,0 +1,1156 @@
+//! Aiming against non-linear target or projectile motion
+
+use core::cmp::Ordering;
+use core::ops::RangeInclusive;
+
+use na::RealField;
+
+use crate::{linear_aim, linear_aim_solutions, AimSolution, Target};
+
+/// A window of time over which target position is a polynomial
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct PolySegment<N: RealField> {
+    /// Time at which the segment begins
+    pub start: N,
+    /// Time at which the segment ends
+    pub end: N,
+    /// Coefficients of the target's position in time since `start`, constant term first
+    pub coefficients: Vec<na::Vector3<N>>,
+}
+
+#[cfg(feature = "std")]
+impl<N: RealField> PolySegment<N> {
+    /// Target position at absolute time `t`
+    pub fn position(&self, t: N) -> na::Vector3<N> {
+        let dt = t - self.start;
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(na::zero(), |acc, &c| acc * dt + c)
+    }
+}
+
+/// Direction to aim a projectile travelling at `speed` from the origin at time zero to hit a
+/// target following piecewise-polynomial `segments`, and time of impact
+///
+/// Segments are searched in order for the earliest intercept within each one's time window.
+#[cfg(feature = "std")]
+pub fn aim_piecewise<N: RealField>(
+    segments: &[PolySegment<N>],
+    speed: N,
+) -> Option<AimSolution<N>> {
+    segments.iter().find_map(|segment| {
+        let t = earliest_root(
+            |t| segment.position(t).norm() - speed * t,
+            segment.start.max(na::zero()),
+            segment.end,
+        )?;
+        let point = segment.position(t);
+        Some(AimSolution {
+            direction: na::Unit::try_new(point, na::zero())?,
+            time: t,
+            point,
+            required_speed: speed,
+        })
+    })
+}
+
+/// Straight-line solution for a projectile at `speed` against a target at `position_at(t)`
+///
+/// Iterates the time of flight `t = |position_at(t)| / speed` from `initial_guess`, which converges
+/// for any target slower than the projectile. Returns `None` if it hasn't converged to within a
+/// relative `sqrt(ε)` after `max_iters` iterations.
+pub fn aim_iter<N: RealField, F: Fn(N) -> na::Vector3<N>>(
+    position_at: F,
+    speed: N,
+    initial_guess: N,
+    max_iters: usize,
+) -> Option<AimSolution<N>> {
+    let tolerance = N::default_epsilon().sqrt();
+    let mut t = initial_guess;
+    for _ in 0..max_iters {
+        let point = position_at(t);
+        let next = point.norm() / speed;
+        if !next.is_finite() {
+            return None;
+        }
+        if (next - t).abs() <= tolerance * next.max(N::one()) {
+            let point = position_at(next);
+            return Some(AimSolution {
+                direction: na::Unit::new_normalize(point),
+                time: next,
+                point,
+                required_speed: speed,
+            });
+        }
+        t = next;
+    }
+    None
+}
+
+/// Among straight-line solutions at each candidate speed, the one with the fastest closing speed
+/// at impact
+///
+/// Both solutions are considered when the target is faster than the projectile, so the head-on
+/// one is preferred over a later tail chase. Useful for penetrating warheads, where impact speed
+/// matters as much as hitting.
+pub fn max_closing_aim<N: RealField>(
+    target: &Target<N>,
+    speed_options: &[N],
+) -> Option<AimSolution<N>> {
+    let closing =
+        |s: &AimSolution<N>| (s.direction.as_ref() * s.required_speed - target.velocity).norm();
+    speed_options
+        .iter()
+        .flat_map(|&speed| {
+            let [early, late] = linear_aim_solutions(target, speed);
+            early.into_iter().chain(late)
+        })
+        .max_by(|x, y| {
+            closing(x)
+                .partial_cmp(&closing(y))
+                .unwrap_or(Ordering::Equal)
+        })
+}
+
+/// Straight-line solution for a projectile at `speed` against the weighted midpoint of `a` and `b`
+///
+/// `weight` interpolates from `a` at zero to `b` at one, with the aim point moving as the two
+/// targets do. Useful for area effects against clustered targets.
+pub fn aim_between<N: RealField>(
+    a: &Target<N>,
+    b: &Target<N>,
+    speed: N,
+    weight: N,
+) -> Option<AimSolution<N>> {
+    let midpoint = Target {
+        position: a.position.lerp(&b.position, weight),
+        velocity: a.velocity.lerp(&b.velocity, weight),
+    };
+    linear_aim(&midpoint, speed)
+}
+
+/// Like `linear_aim`, for a projectile launched at `speed` that accelerates at `accel` along its
+/// flight
+///
+/// Finds the earliest time at which the distance flown, `speed * t + accel * t² / 2`, reaches the
+/// target's range. A decelerating projectile is considered only until it comes to rest. Agrees
+/// with `linear_aim` when `accel` is zero.
+pub fn linear_aim_accel<N: RealField>(
+    target: &Target<N>,
+    speed: N,
+    accel: N,
+) -> Option<AimSolution<N>> {
+    if accel == na::zero() {
+        return linear_aim(target, speed);
+    }
+    let half = na::convert::<_, N>(0.5);
+    let gap =
+        |t: N| (target.position + target.velocity * t).norm() - (speed + half * accel * t) * t;
+    let horizon = if accel < na::zero() {
+        -speed / accel
+    } else {
+        // Outruns any point the target can reach by then
+        let excess = speed - target.velocity.norm();
+        let range = target.position.norm();
+        (-excess + (excess * excess + na::convert::<_, N>(2.0) * accel * range).sqrt()) / accel
+    };
+    let t = earliest_root(gap, na::zero(), horizon)?;
+    let point = target.position + target.velocity * t;
+    Some(AimSolution {
+        direction: na::Unit::new_normalize(point),
+        time: t,
+        point,
+        required_speed: speed,
+    })
+}
+
+/// How long ago a projectile launched from `launch_point` at `speed` must have been fired to
+/// intercept `target` now
+///
+/// `target` is the target's current state in the same frame as `launch_point`. Since the intercept
+/// point is the target's current position, its velocity only determines where it was at launch,
+/// and the projectile must have been aimed straight at the intercept point. Returns `None` if
+/// `speed` is not positive.
+pub fn backtrack_launch<N: RealField>(
+    launch_point: &na::Point3<N>,
+    target: &Target<N>,
+    speed: N,
+) -> Option<N> {
+    if speed <= na::zero() {
+        return None;
+    }
+    Some((target.position - launch_point.coords).norm() / speed)
+}
+
+/// Smallest launch speed which intercepts `target` in a straight line within `deadline`
+///
+/// The speed needed to intercept after time `t` is `|p + v t| / t`, whose square is quadratic in
+/// `1 / t`, so the minimum is found in closed form. It's reached at the deadline unless the target
+/// is easier to reach sooner. Returns `None` if `deadline` is not positive.
+pub fn min_speed_for_deadline<N: RealField>(target: &Target<N>, deadline: N) -> Option<N> {
+    if deadline <= na::zero() {
+        return None;
+    }
+    let latest = N::one() / deadline;
+    let range_squared = target.position.norm_squared();
+    let rate = if range_squared > na::zero() {
+        (-target.position.dot(&target.velocity) / range_squared).max(latest)
+    } else {
+        latest
+    };
+    Some((target.position * rate + target.velocity).norm())
+}
+
+/// Signed lead angles for a top-down chart of straight-line intercepts
+///
+/// The target lies along the x axis and moves in the xy plane. Row `i` has the target heading
+/// `2π * i / samples` counterclockwise from the line of sight, so heading zero flies directly
+/// away; column `j` has target speed `target_speed * j / (samples - 1)`. Each cell is the
+/// counterclockwise angle from the line of sight to the aim direction from `linear_aim` at
+/// `own_speed`, or `None` if there's no intercept. Lead angles don't depend on range.
+#[cfg(feature = "std")]
+pub fn lead_angle_field<N: RealField>(
+    own_speed: N,
+    target_speed: N,
+    samples: usize,
+) -> Vec<Vec<Option<N>>> {
+    let column_step = if samples > 1 {
+        target_speed / na::convert((samples - 1) as f64)
+    } else {
+        na::zero()
+    };
+    (0..samples)
+        .map(|i| {
+            let heading = N::two_pi() * na::convert(i as f64 / samples as f64);
+            (0..samples)
+                .map(|j| {
+                    let speed = column_step * na::convert(j as f64);
+                    let target = Target {
+                        position: na::Vector3::x(),
+                        velocity: na::Vector3::new(heading.cos(), heading.sin(), na::zero())
+                            * speed,
+                    };
+                    let direction = linear_aim(&target, own_speed)?.direction;
+                    Some(direction.y.atan2(direction.x))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Gradient of `linear_aim`'s intercept time at `speed` with respect to the launcher's own velocity
+///
+/// `target` is relative to the launcher, as in `MovingFrame::target`, so changing the launcher's
+/// velocity by `dv` changes the target's relative velocity by `-dv`. Turning against the component
+/// of the gradient perpendicular to the launcher's velocity brings the earliest intercept sooner.
+/// Returns `None` if there's no intercept.
+pub fn tti_gradient<N: RealField>(target: &Target<N>, speed: N) -> Option<na::Vector3<N>> {
+    let t = linear_aim(target, speed)?.time;
+    // Implicit differentiation of |p + v t|² = s² t²
+    let impact = target.position + target.velocity * t;
+    let slope = impact.dot(&target.velocity) - speed * speed * t;
+    if slope.abs() <= N::default_epsilon() {
+        return None;
+    }
+    Some(impact * (t / slope))
+}
+
+/// Distance by which holding `current_velocity` would miss the intercept point `linear_aim` finds
+/// at `speed`
+///
+/// Measured perpendicular to the current path, so a vehicle already on the intercept heading has
+/// no cross-range error. Returns `None` if there's no intercept or `current_velocity` is zero.
+pub fn cross_range_error<N: RealField>(
+    target: &Target<N>,
+    speed: N,
+    current_velocity: &na::Vector3<N>,
+) -> Option<N> {
+    let point = linear_aim(target, speed)?.point;
+    let forward = current_velocity.try_normalize(N::default_epsilon())?;
+    Some((point - forward * forward.dot(&point)).norm())
+}
+
+/// Outcome of `fire_decision`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FireDecision<N: RealField> {
+    /// An intercept is available now
+    FireNow,
+    /// The target will enter the envelope after this much time
+    Wait(N),
+    /// The target won't enter the envelope within the allowed wait
+    NoSolution,
+}
+
+/// Decide whether to fire a single projectile at `speed` now, or wait for a constant-velocity
+/// `target` to enter the envelope
+///
+/// The envelope is the set of geometries with a straight-line intercept taking no longer than
+/// `max_flight_time`, e.g. due to limited fuel or fuzing. Firing as soon as the target is in the
+/// envelope gives the earliest impact, so waiting is only advised when it isn't yet.
+pub fn fire_decision<N: RealField>(
+    target: &Target<N>,
+    speed: N,
+    max_flight_time: N,
+    max_wait: N,
+) -> FireDecision<N> {
+    let excess_flight_time = |wait: N| {
+        let future = Target {
+            position: target.position + target.velocity * wait,
+            velocity: target.velocity,
+        };
+        linear_aim(&future, speed).map_or(N::max_value(), |s| s.time - max_flight_time)
+    };
+    if excess_flight_time(na::zero()) <= na::zero() {
+        return FireDecision::FireNow;
+    }
+    match earliest_root(excess_flight_time, na::zero(), max_wait) {
+        Some(wait) => FireDecision::Wait(wait),
+        None => FireDecision::NoSolution,
+    }
+}
+
+/// Whether a projectile at `speed` can intercept `target` were it seen along each of `samples`
+/// evenly distributed directions at its current range
+///
+/// Straight-line intercept feasibility depends only on the bearing of the target relative to its
+/// velocity, so this describes every launch geometry against a target of that velocity.
+#[cfg(feature = "std")]
+pub fn launch_envelope<N: RealField>(
+    target: &Target<N>,
+    speed: N,
+    samples: usize,
+) -> Vec<(na::Unit<na::Vector3<N>>, bool)> {
+    let range = target.position.norm();
+    (0..samples)
+        .map(|i| {
+            // Fibonacci lattice
+            let golden = (N::one() + na::convert::<_, N>(5.).sqrt()) * na::convert(0.5);
+            let i = na::convert::<_, N>(i as f64);
+            let z =
+                N::one() - (i + na::convert(0.5)) * na::convert(2.) / na::convert(samples as f64);
+            let phi = N::two_pi() * i / golden;
+            let r = (N::one() - z * z).sqrt();
+            let direction =
+                na::Unit::new_unchecked(na::Vector3::new(r * phi.cos(), r * phi.sin(), z));
+            (direction, interceptable(target, &direction, range, speed))
+        })
+        .collect()
+}
+
+/// Frontier of `launch_envelope`, as found by `envelope_boundary`
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnvelopeBoundary<N: RealField> {
+    /// Every direction is interceptable, as when the projectile is faster than the target
+    Everywhere,
+    /// No direction is interceptable, as for non-finite inputs
+    Nowhere,
+    /// Ordered directions separating interceptable from non-interceptable ones, forming a closed
+    /// loop
+    Frontier(Vec<na::Unit<na::Vector3<N>>>),
+}
+
+/// The frontier of `launch_envelope`
+///
+/// One point is found on each of `samples` meridians about the head-on direction, assuming
+/// feasibility decreases monotonically away from head-on.
+#[cfg(feature = "std")]
+pub fn envelope_boundary<N: RealField>(
+    target: &Target<N>,
+    speed: N,
+    samples: usize,
+) -> EnvelopeBoundary<N> {
+    let range = target.position.norm();
+    let uniform = |feasible| {
+        if feasible {
+            EnvelopeBoundary::Everywhere
+        } else {
+            EnvelopeBoundary::Nowhere
+        }
+    };
+    let axis = match na::Unit::try_new(-target.velocity, N::default_epsilon()) {
+        Some(axis) => axis,
+        // A stationary target is equally interceptable in every direction
+        None => return uniform(interceptable(target, &na::Vector3::z_axis(), range, speed)),
+    };
+    let reference = if axis.x.abs() < na::convert(0.9) {
+        na::Vector3::x()
+    } else {
+        na::Vector3::y()
+    };
+    let perp = na::Unit::new_normalize(axis.cross(&reference));
+    let meridian = |azimuth: N, polar: N| {
+        let tilt = na::Rotation3::from_axis_angle(&perp, polar);
+        na::Rotation3::from_axis_angle(&axis, azimuth) * (tilt * axis)
+    };
+    // Every meridian shares its endpoints, head-on and tail-on
+    let head_on = interceptable(target, &axis, range, speed);
+    if !head_on || interceptable(target, &-axis, range, speed) {
+        return uniform(head_on);
+    }
+    let mut boundary = Vec::with_capacity(samples);
+    for i in 0..samples {
+        let azimuth = N::two_pi() * na::convert(i as f64) / na::convert(samples as f64);
+        let polar = bisect(
+            |polar| {
+                if interceptable(target, &meridian(azimuth, polar), range, speed) {
+                    N::one()
+                } else {
+                    -N::one()
+                }
+            },
+            na::zero(),
+            N::pi(),
+        );
+        boundary.push(meridian(azimuth, polar));
+    }
+    EnvelopeBoundary::Frontier(boundary)
+}
+
+#[cfg(feature = "std")]
+fn interceptable<N: RealField>(
+    target: &Target<N>,
+    direction: &na::Unit<na::Vector3<N>>,
+    range: N,
+    speed: N,
+) -> bool {
+    let target = Target {
+        position: direction.as_ref() * range,
+        velocity: target.velocity,
+    };
+    linear_aim(&target, speed).is_some()
+}
+
+/// Unguided projectile dynamics, for aim solvers that account for more than straight-line flight
+pub trait Ballistics<N: RealField> {
+    /// Displacement from the launch point after `time` of a projectile launched along `direction`
+    fn displacement(&self, direction: &na::Unit<na::Vector3<N>>, time: N) -> na::Vector3<N>;
+    /// Velocity after `time` of a projectile launched along `direction`
+    fn velocity(&self, direction: &na::Unit<na::Vector3<N>>, time: N) -> na::Vector3<N>;
+}
+
+/// A projectile launched at `speed` under constant `gravity`, without drag
+#[derive(Debug, Copy, Clone)]
+pub struct Vacuum<N: RealField> {
+    pub speed: N,
+    pub gravity: na::Vector3<N>,
+}
+
+impl<N: RealField> Ballistics<N> for Vacuum<N> {
+    fn displacement(&self, direction: &na::Unit<na::Vector3<N>>, time: N) -> na::Vector3<N> {
+        direction.as_ref() * (self.speed * time)
+            + self.gravity * (na::convert::<_, N>(0.5) * time * time)
+    }
+
+    fn velocity(&self, direction: &na::Unit<na::Vector3<N>>, time: N) -> na::Vector3<N> {
+        direction.as_ref() * self.speed + self.gravity * time
+    }
+}
+
+/// Improve an aim solution, e.g. from `linear_aim`, against the true projectile dynamics `model`
+///
+/// Each iteration propagates the projectile to the current time of impact, then corrects the
+/// time by the along-track error and the aim point by the remaining cross-track error. More
+/// iterations trade time for accuracy.
+pub fn refine_aim<N: RealField>(
+    initial_guess: &AimSolution<N>,
+    target: &Target<N>,
+    model: &impl Ballistics<N>,
+    iterations: usize,
+) -> AimSolution<N> {
+    let (mut direction, mut t) = (initial_guess.direction, initial_guess.time);
+    let mut aim_point = direction.as_ref() * (target.position + target.velocity * t).norm();
+    for _ in 0..iterations {
+        let miss = target.position + target.velocity * t - model.displacement(&direction, t);
+        let closing = model.velocity(&direction, t) - target.velocity;
+        let dt = miss.dot(&closing) / closing.norm_squared();
+        t += dt;
+        aim_point += miss - closing * dt;
+        direction = na::Unit::new_normalize(aim_point);
+    }
+    AimSolution {
+        direction,
+        time: t,
+        point: model.displacement(&direction, t),
+        required_speed: initial_guess.required_speed,
+    }
+}
+
+/// Like `linear_aim`, for an unguided projectile launched at `speed` under constant `gravity`
+///
+/// Finds the earliest impact, i.e. the low-angle solution when there's also a lob, as a root of
+/// `|p + v t - g t² / 2| = s t`. Agrees with `linear_aim` when `gravity` is zero. Returns `None` if
+/// the target is out of range; targets at the very limit of range, which is reached only over a
+/// narrow window of flight times, may be missed.
+pub fn ballistic_aim<N: RealField>(
+    target: &Target<N>,
+    speed: N,
+    gravity: &na::Vector3<N>,
+) -> Option<AimSolution<N>> {
+    let g = gravity.norm();
+    if g == na::zero() {
+        return linear_aim(target, speed);
+    }
+    let half = na::convert::<_, N>(0.5);
+    // Launch displacement required to hit at time `t`
+    let aim = |t: N| target.position + target.velocity * t - gravity * (half * t * t);
+    let gap = |t: N| aim(t).norm() - speed * t;
+    // Beyond this, gravity carries the projectile past anywhere the target can reach
+    let reach = speed + target.velocity.norm();
+    let horizon = (reach
+        + (reach * reach + na::convert::<_, N>(2.0) * g * target.position.norm()).sqrt())
+        / g;
+    let t = earliest_root(gap, na::zero(), horizon)?;
+    Some(AimSolution {
+        direction: na::Unit::new_normalize(aim(t)),
+        time: t,
+        point: target.position + target.velocity * t,
+        required_speed: speed,
+    })
+}
+
+/// Slowest launch reaching a stationary target at `target_position` under constant `gravity`
+///
+/// The launch velocity for a flight time `t` is `d / t - g t / 2`, whose magnitude is least at
+/// `t = sqrt(2 |d| / |g|)`, requiring a speed of `sqrt(|g| |d| - d·g)`. On flat ground this is the
+/// familiar 45 degree elevation reaching a range of `v² / |g|`. Returns `None` if `gravity` is
+/// zero, since then any speed suffices given time, or if the target is at the launch point, where
+/// no direction is needed.
+pub fn min_energy_aim<N: RealField>(
+    target_position: &na::Vector3<N>,
+    gravity: na::Vector3<N>,
+) -> Option<AimSolution<N>> {
+    let range = target_position.norm();
+    let g = gravity.norm();
+    if range <= N::default_epsilon() || g <= N::default_epsilon() {
+        return None;
+    }
+    let t = (na::convert::<_, N>(2.0) * range / g).sqrt();
+    let velocity = target_position / t - gravity * (t * na::convert(0.5));
+    Some(AimSolution {
+        direction: na::Unit::new_normalize(velocity),
+        time: t,
+        point: *target_position,
+        required_speed: (g * range - target_position.dot(&gravity)).sqrt(),
+    })
+}
+
+/// Like `linear_aim`, for a projectile launched at `initial_speed` and slowed by linear drag
+///
+/// Speed decays as `v0 * exp(-k t)`, with `k` the `drag_coefficient`, so the projectile never
+/// travels further than `v0 / k`. The impact time is bracketed within the interval during which
+/// the target is inside that reach, and found as the earliest root of
+/// `|p + v t| = v0 / k (1 - exp(-k t))`. Agrees with `linear_aim` when `drag_coefficient` is zero.
+/// Returns `None` if the target never comes within reach; windows of reach too brief to be sampled
+/// may be missed.
+pub fn linear_aim_drag<N: RealField>(
+    target: &Target<N>,
+    initial_speed: N,
+    drag_coefficient: N,
+) -> Option<AimSolution<N>> {
+    let k = drag_coefficient;
+    if k == na::zero() {
+        return linear_aim(target, initial_speed);
+    }
+    let reach = initial_speed / k;
+    let (p, v) = (&target.position, &target.velocity);
+    let distance = |t: N| reach * (N::one() - (-k * t).exp());
+    let t = if v.norm_squared() == na::zero() {
+        let range = p.norm();
+        if range >= reach {
+            return None;
+        }
+        -(N::one() - range / reach).ln() / k
+    } else {
+        // Solve |p + v t| = reach
+        let a = v.norm_squared();
+        let b = p.dot(v);
+        let discriminant = b * b - a * (p.norm_squared() - reach * reach);
+        if discriminant < na::zero() {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        let (enter, exit) = ((-b - root) / a, (-b + root) / a);
+        let gap = |t: N| (p + v * t).norm() - distance(t);
+        earliest_root(gap, enter.max(na::zero()), exit)?
+    };
+    let point = p + v * t;
+    Some(AimSolution {
+        direction: na::Unit::new_normalize(point),
+        time: t,
+        point,
+        required_speed: initial_speed,
+    })
+}
+
+/// Smallest miss distance achievable by a projectile launched along the fixed `direction` at a
+/// speed within `speed_range`, and the speed achieving it
+///
+/// For launchers which can't be slewed, e.g. a fixed tube. Speeds are sampled across the range and
+/// the best sample refined by golden-section search, so narrow minima between samples may be
+/// missed.
+pub fn min_miss_fixed_direction<N: RealField>(
+    target: &Target<N>,
+    direction: &na::Unit<na::Vector3<N>>,
+    speed_range: RangeInclusive<N>,
+) -> (N, N) {
+    const SAMPLES: usize = 64;
+    let (start, end) = (*speed_range.start(), *speed_range.end());
+    let miss = |speed: N| fixed_direction_miss(target, direction, speed);
+    let step = (end - start) / na::convert(SAMPLES as f64);
+    let speed_at = |i: usize| start + step * na::convert(i as f64);
+    let best = (0..=SAMPLES)
+        .min_by(|&a, &b| {
+            miss(speed_at(a))
+                .partial_cmp(&miss(speed_at(b)))
+                .unwrap_or(Ordering::Equal)
+        })
+        .unwrap();
+    let mut lo = speed_at(best.saturating_sub(1));
+    let mut hi = speed_at((best + 1).min(SAMPLES));
+    let ratio = na::convert::<_, N>((5.0f64.sqrt() - 1.0) / 2.0);
+    for _ in 0..64 {
+        let a = hi - (hi - lo) * ratio;
+        let b = lo + (hi - lo) * ratio;
+        if miss(a) < miss(b) {
+            hi = b;
+        } else {
+            lo = a;
+        }
+    }
+    let speed = (lo + hi) * na::convert(0.5);
+    (miss(speed), speed)
+}
+
+/// Closest approach between `target` and a projectile fired along `direction` at `speed`
+fn fixed_direction_miss<N: RealField>(
+    target: &Target<N>,
+    direction: &na::Unit<na::Vector3<N>>,
+    speed: N,
+) -> N {
+    let closing = target.velocity - direction.as_ref() * speed;
+    let t = (-target.position.dot(&closing) / closing.norm_squared()).max(na::zero());
+    if t.is_finite() {
+        (target.position + closing * t).norm()
+    } else {
+        target.position.norm()
+    }
+}
+
+/// Earliest time in `[start, end]` at which `f` falls to zero from above
+pub(crate) fn earliest_root<N: RealField>(f: impl Fn(N) -> N, start: N, end: N) -> Option<N> {
+    const SAMPLES: usize = 64;
+    if end <= start {
+        return None;
+    }
+    let step = (end - start) / na::convert(SAMPLES as f64);
+    let mut lo = start;
+    if f(lo) <= na::zero() {
+        return Some(lo);
+    }
+    for i in 1..=SAMPLES {
+        let hi = start + step * na::convert(i as f64);
+        if f(hi) <= na::zero() {
+            return Some(bisect(&f, lo, hi));
+        }
+        lo = hi;
+    }
+    None
+}
+
+/// Refine a bracket where `f(lo) > 0 >= f(hi)`
+fn bisect<N: RealField>(f: impl Fn(N) -> N, mut lo: N, mut hi: N) -> N {
+    for _ in 0..64 {
+        let mid = (lo + hi) * na::convert(0.5);
+        if f(mid) > na::zero() {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    hi
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_direction() {
+        let target = Target {
+            position: na::Vector3::new(3000.0, 1000.0, 0.0),
+            velocity: na::Vector3::new(-100.0, 0.0, 50.0),
+        };
+        // Never points at the target's track, so intercept is impossible
+        let direction = na::Unit::new_normalize(na::Vector3::new(1.0, 0.4, 0.0));
+        let (miss, speed) = min_miss_fixed_direction(&target, &direction, 100.0..=1000.0);
+        assert!(miss > 1.0);
+        let (brute_miss, brute_speed) = (0..=100_000)
+            .map(|i| {
+                let speed = 100.0 + 900.0 * f64::from(i) / 100_000.0;
+                (fixed_direction_miss(&target, &direction, speed), speed)
+            })
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
+            .unwrap();
+        assert!(miss <= brute_miss + 1e-9);
+        assert!(
+            (speed - brute_speed).abs() < 1e-1,
+            "{} {}",
+            speed,
+            brute_speed
+        );
+    }
+
+    #[test]
+    fn piecewise_second_segment() {
+        // Target flies away from the origin, then turns back towards it at t = 2
+        let segments = [
+            PolySegment {
+                start: 0.0,
+                end: 2.0,
+                coefficients: vec![
+                    na::Vector3::new(1000.0, 0.0, 0.0),
+                    na::Vector3::new(200.0, 0.0, 0.0),
+                ],
+            },
+            PolySegment {
+                start: 2.0,
+                end: 10.0,
+                coefficients: vec![
+                    na::Vector3::new(1400.0, 0.0, 0.0),
+                    na::Vector3::new(-100.0, 50.0, 0.0),
+                    na::Vector3::new(0.0, 0.0, 5.0),
+                ],
+            },
+        ];
+        let solution = aim_piecewise(&segments, 400.0).unwrap();
+        let t = solution.time;
+        assert!(t > 2.0 && t < 10.0);
+        let hit = solution.direction.into_inner() * 400.0 * t;
+        assert!((hit - segments[1].position(t)).norm() < 1e-6);
+        assert_eq!(solution.point, segments[1].position(t));
+        assert!(aim_piecewise(&segments[..1], 400.0).is_none());
+    }
+
+    #[test]
+    fn max_closing() {
+        // Target fleeing away from the launcher across its line of sight
+        let target = Target {
+            position: na::Vector3::new(0.0, 1000.0, 0.0),
+            velocity: na::Vector3::new(100.0, 50.0, 0.0),
+        };
+        let options = [200.0, 400.0, 800.0, 1600.0];
+        let best = max_closing_aim(&target, &options).unwrap();
+        assert_eq!(best.required_speed, 1600.0);
+        let hit = target.position + target.velocity * best.time;
+        assert!((best.point - hit).norm() < 1e-6);
+
+        // Head-on target: every solution is directly ahead, so closing speed is simply additive
+        let head_on = Target {
+            position: na::Vector3::new(0.0, 1000.0, 0.0),
+            velocity: na::Vector3::new(0.0, -300.0, 0.0),
+        };
+        let best = max_closing_aim(&head_on, &options[..2]).unwrap();
+        assert_eq!(best.required_speed, 400.0);
+        assert!(max_closing_aim(&head_on, &[]).is_none());
+
+        // Target outrunning the projectile across its path, hittable early head-on or late from
+        // behind
+        let crossing = Target {
+            position: na::Vector3::new(0.0, 2000.0, 0.0),
+            velocity: na::Vector3::new(300.0, -400.0, 0.0),
+        };
+        let [early, late] = linear_aim_solutions(&crossing, 400.0);
+        let (early, late) = (early.unwrap(), late.unwrap());
+        assert!(early.time < late.time);
+        let best = max_closing_aim(&crossing, &[400.0]).unwrap();
+        assert_eq!(best, early);
+        let closing =
+            |s: &AimSolution<f64>| (s.direction.into_inner() * 400.0 - crossing.velocity).norm();
+        assert!(closing(&best) > 4.0 * closing(&late));
+    }
+
+    #[test]
+    fn between() {
+        let a = Target {
+            position: na::Vector3::new(1000.0, 200.0, 0.0),
+            velocity: na::Vector3::new(-50.0, 0.0, 10.0),
+        };
+        let b = Target {
+            position: na::Vector3::new(1200.0, -200.0, 50.0),
+            velocity: na::Vector3::new(-30.0, 20.0, 0.0),
+        };
+        let solution = aim_between(&a, &b, 400.0, 0.5).unwrap();
+        let midpoint = |t: f64| (a.position + a.velocity * t + b.position + b.velocity * t) * 0.5;
+        assert!((solution.point - midpoint(solution.time)).norm() < 1e-6);
+        let at_a = aim_between(&a, &b, 400.0, 0.0).unwrap();
+        assert_eq!(Some(at_a), linear_aim(&a, 400.0));
+    }
+
+    #[test]
+    fn backtrack() {
+        let launch_point = na::Point3::new(10.0, -5.0, 0.0);
+        let target = Target {
+            position: na::Vector3::new(400.0, 300.0, 50.0),
+            velocity: na::Vector3::new(-20.0, 10.0, 0.0),
+        };
+        let ago = backtrack_launch(&launch_point, &target, 100.0).unwrap();
+        let direction = (target.position - launch_point.coords).normalize();
+        let mut projectile = launch_point.coords;
+        let steps = 1000;
+        for _ in 0..steps {
+            projectile += direction * 100.0 * (ago / f64::from(steps));
+        }
+        assert!((projectile - target.position).norm() < 1e-6);
+        assert_eq!(backtrack_launch(&launch_point, &target, 0.0), None);
+    }
+
+    #[test]
+    fn lead_angles() {
+        const SAMPLES: usize = 16;
+        // Slower than the target, so some geometries can't be intercepted
+        let field = lead_angle_field(200.0f64, 300.0, SAMPLES);
+        assert_eq!(field.len(), SAMPLES);
+        for (i, row) in field.iter().enumerate() {
+            assert_eq!(row.len(), SAMPLES);
+            // Headings mirrored across the line of sight lead by mirrored angles
+            let mirror = &field[(SAMPLES - i) % SAMPLES];
+            for (a, b) in row.iter().zip(mirror) {
+                match (a, b) {
+                    (Some(a), Some(b)) => assert!((a + b).abs() < 1e-9),
+                    (None, None) => {}
+                    _ => panic!("asymmetric field"),
+                }
+            }
+        }
+        // A stationary target needs no lead
+        assert!(field.iter().all(|row| row[0] == Some(0.0)));
+        // A faster target flying directly away can't be caught
+        assert_eq!(field[0][SAMPLES - 1], None);
+        // A target crossing towards +y is led towards +y
+        assert!(field[SAMPLES / 4][SAMPLES / 2].unwrap() > 0.0);
+    }
+
+    #[test]
+    fn boosted_aim() {
+        let target = Target::<f64> {
+            position: na::Vector3::new(0.0, 4000.0, 0.0),
+            velocity: na::Vector3::new(300.0, 0.0, 0.0),
+        };
+        assert_eq!(
+            linear_aim_accel(&target, 500.0, 0.0),
+            linear_aim(&target, 500.0)
+        );
+        let coasting = linear_aim(&target, 500.0).unwrap();
+        let boosted = linear_aim_accel(&target, 500.0, 100.0).unwrap();
+        let (t, flown) = (
+            boosted.time,
+            500.0 * boosted.time + 50.0 * boosted.time.powi(2),
+        );
+        let impact = boosted.direction.into_inner() * flown;
+        assert!((impact - target.position - target.velocity * t).norm() < 1e-6);
+        assert!(boosted.time < coasting.time - 1.0);
+        assert!((boosted.point - coasting.point).norm() > 500.0);
+        // A projectile that stops short never arrives
+        assert_eq!(linear_aim_accel(&target, 500.0, -20.0), None);
+    }
+
+    #[test]
+    fn deadline_speed() {
+        let target = Target::<f64> {
+            position: na::Vector3::new(0.0, 5000.0, 0.0),
+            velocity: na::Vector3::new(-100.0, 100.0, 0.0),
+        };
+        let speed = min_speed_for_deadline(&target, 10.0).unwrap();
+        assert!((linear_aim(&target, speed).unwrap().time - 10.0).abs() < 1e-6);
+        let slower = linear_aim(&target, speed * 0.99).unwrap();
+        assert!(slower.time > 10.0);
+        assert_eq!(min_speed_for_deadline(&target, 0.0), None);
+
+        // Arriving by itself before the deadline
+        let inbound = Target::<f64> {
+            position: na::Vector3::new(0.0, 5000.0, 0.0),
+            velocity: na::Vector3::new(0.0, -100.0, 0.0),
+        };
+        assert!(min_speed_for_deadline(&inbound, 100.0).unwrap() < 1e-9);
+    }
+
+    #[test]
+    fn cross_range() {
+        let target = Target::<f64> {
+            position: na::Vector3::new(0.0, 4000.0, 0.0),
+            velocity: na::Vector3::new(300.0, 0.0, 0.0),
+        };
+        // 500 t = |(300 t, 4000)| gives t = 10, intercepting at (3000, 4000)
+        let solution = linear_aim(&target, 500.0).unwrap();
+        assert!((solution.time - 10.0).abs() < 1e-9);
+        let on_heading = solution.direction.into_inner() * 500.0;
+        assert!(cross_range_error(&target, 500.0, &on_heading).unwrap() < 1e-9);
+        // Heading straight up the y axis passes 3000 to the side
+        let straight = na::Vector3::new(0.0, 500.0, 0.0);
+        let error = cross_range_error(&target, 500.0, &straight).unwrap();
+        assert!((error - 3000.0).abs() < 1e-9);
+        assert_eq!(cross_range_error(&target, 500.0, &na::zero()), None);
+    }
+
+    #[test]
+    fn intercept_time_gradient() {
+        let target = Target {
+            position: na::Vector3::new(3000.0, 1000.0, 0.0),
+            velocity: na::Vector3::new(-100.0, 50.0, 20.0),
+        };
+        let own_velocity = na::Vector3::new(200.0, 0.0, 0.0);
+        let speed = 600.0;
+        let relative = |own: &na::Vector3<f64>| Target {
+            position: target.position,
+            velocity: target.velocity - own,
+        };
+        let time = |own: &na::Vector3<f64>| linear_aim(&relative(own), speed).unwrap().time;
+        let gradient = tti_gradient(&relative(&own_velocity), speed).unwrap();
+        // Agrees with finite differences
+        for axis in 0..3 {
+            let mut dv = na::Vector3::zeros();
+            dv[axis] = 1e-3;
+            let numeric = (time(&(own_velocity + dv)) - time(&(own_velocity - dv))) / 2e-3;
+            assert!((numeric - gradient[axis]).abs() < 1e-6);
+        }
+        // Turning away from the gradient at constant speed intercepts sooner
+        let forward = own_velocity.normalize();
+        let turn = gradient - forward * forward.dot(&gradient);
+        let turned = (own_velocity - turn.normalize() * 10.0).normalize() * own_velocity.norm();
+        assert!(time(&turned) < time(&own_velocity));
+    }
+
+    #[test]
+    fn fire_or_wait() {
+        let mut target = Target::<f64> {
+            position: na::Vector3::new(0.0, 20_000.0, 500.0),
+            velocity: na::Vector3::new(0.0, -300.0, 0.0),
+        };
+        // In range once the flight time falls to 10s
+        let wait = match fire_decision(&target, 500.0, 10.0, 60.0) {
+            FireDecision::Wait(wait) => wait,
+            x => panic!("unexpected {:?}", x),
+        };
+        let future = Target {
+            position: target.position + target.velocity * wait,
+            velocity: target.velocity,
+        };
+        assert!((linear_aim(&future, 500.0).unwrap().time - 10.0).abs() < 1e-6);
+        assert_eq!(
+            fire_decision(&target, 500.0, 10.0, 30.0),
+            FireDecision::NoSolution
+        );
+        target.position += target.velocity * wait;
+        assert_eq!(
+            fire_decision(&target, 500.0, 10.0, 60.0),
+            FireDecision::FireNow
+        );
+    }
+
+    #[test]
+    fn envelope() {
+        let stationary = Target {
+            position: na::Vector3::new(0.0, 1000.0, 0.0),
+            velocity: na::zero(),
+        };
+        let envelope = launch_envelope(&stationary, 300.0, 200);
+        assert_eq!(envelope.len(), 200);
+        assert!(envelope.iter().all(|&(_, ok)| ok));
+        assert_eq!(
+            envelope_boundary(&stationary, 300.0, 32),
+            EnvelopeBoundary::Everywhere
+        );
+        let slow = Target {
+            velocity: na::Vector3::new(100.0, 0.0, 0.0),
+            ..stationary
+        };
+        assert_eq!(
+            envelope_boundary(&slow, 300.0, 32),
+            EnvelopeBoundary::Everywhere
+        );
+        assert_eq!(
+            envelope_boundary(&slow, f64::NAN, 32),
+            EnvelopeBoundary::Nowhere
+        );
+        assert_eq!(
+            envelope_boundary(&stationary, f64::NAN, 32),
+            EnvelopeBoundary::Nowhere
+        );
+
+        // A target faster than the projectile can only be caught within a cone about head-on
+        let fast = Target::<f64> {
+            position: na::Vector3::new(0.0, 1000.0, 0.0),
+            velocity: na::Vector3::new(600.0, 0.0, 0.0),
+        };
+        assert!(launch_envelope(&fast, 300.0, 200)
+            .iter()
+            .any(|&(_, ok)| !ok));
+        let boundary = match envelope_boundary(&fast, 300.0, 32) {
+            EnvelopeBoundary::Frontier(boundary) => boundary,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(boundary.len(), 32);
+        let half_angle = (300.0f64 / 600.0).asin();
+        for direction in &boundary {
+            assert!((direction.angle(&-na::Vector3::x()) - half_angle).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn ballistic() {
+        let gravity = na::Vector3::new(0.0, 0.0, -9.81);
+        let model = Vacuum {
+            speed: 100.0,
+            gravity,
+        };
+        // Range on flat ground is v² sin(2θ) / g
+        let ground = Target::<f64> {
+            position: na::Vector3::new(500.0, 0.0, 0.0),
+            velocity: na::zero(),
+        };
+        let solution = ballistic_aim(&ground, 100.0, &gravity).unwrap();
+        let elevation = (500.0 * 9.81 / 100.0f64.powi(2)).asin() / 2.0;
+        assert!((solution.direction.z.asin() - elevation).abs() < 1e-9);
+        assert!((solution.time - 500.0 / (100.0 * elevation.cos())).abs() < 1e-9);
+        let beyond = Target {
+            position: na::Vector3::new(2000.0, 0.0, 0.0),
+            ..ground
+        };
+        assert_eq!(ballistic_aim(&beyond, 100.0, &gravity), None);
+
+        let moving = Target::<f64> {
+            position: na::Vector3::new(600.0, 200.0, 50.0),
+            velocity: na::Vector3::new(-30.0, 40.0, 0.0),
+        };
+        let solution = ballistic_aim(&moving, 100.0, &gravity).unwrap();
+        let t = solution.time;
+        let impact = model.displacement(&solution.direction, t);
+        assert!((impact - (moving.position + moving.velocity * t)).norm() < 1e-6);
+        assert!((impact - solution.point).norm() < 1e-6);
+
+        assert_eq!(
+            ballistic_aim(&moving, 100.0, &na::zero()),
+            linear_aim(&moving, 100.0)
+        );
+    }
+
+    #[test]
+    fn min_energy() {
+        let gravity = na::Vector3::new(0.0, 0.0, -9.81);
+        let ground = na::Vector3::new(500.0f64, 0.0, 0.0);
+        let solution = min_energy_aim(&ground, gravity).unwrap();
+        assert!((solution.required_speed - (500.0 * 9.81f64).sqrt()).abs() < 1e-9);
+        assert!((solution.direction.z.asin().to_degrees() - 45.0).abs() < 1e-9);
+
+        let elevated = na::Vector3::new(400.0, 300.0, 200.0);
+        let solution = min_energy_aim(&elevated, gravity).unwrap();
+        let model = Vacuum {
+            speed: solution.required_speed,
+            gravity,
+        };
+        let impact = model.displacement(&solution.direction, solution.time);
+        assert!((impact - elevated).norm() < 1e-9);
+        let target = Target {
+            position: elevated,
+            velocity: na::zero(),
+        };
+        // Any slower and the target is out of reach
+        let slower = solution.required_speed * 0.99;
+        assert_eq!(ballistic_aim(&target, slower, &gravity), None);
+        assert!(ballistic_aim(&target, solution.required_speed * 1.01, &gravity).is_some());
+
+        assert_eq!(min_energy_aim(&na::zero(), gravity), None);
+        assert_eq!(min_energy_aim(&elevated, na::zero()), None);
+    }
+
+    #[test]
+    fn iterative() {
+        let target = Target::<f64> {
+            position: na::Vector3::new(3000.0, 1000.0, 200.0),
+            velocity: na::Vector3::new(-100.0, 250.0, 0.0),
+        };
+        let linear = |t| target.predict(t).position;
+        let solution = aim_iter(linear, 600.0, 0.0, 100).unwrap();
+        let expected = linear_aim(&target, 600.0).unwrap();
+        assert!((solution.time - expected.time).abs() < 1e-6);
+        assert!(solution.direction.angle(&expected.direction) < 1e-6);
+
+        // Circling at 200 m/s
+        let circling = |t: f64| {
+            let angle = t / 10.0;
+            na::Vector3::new(2000.0 * angle.cos(), 2000.0 * angle.sin(), 500.0)
+        };
+        let solution = aim_iter(circling, 600.0, 0.0, 100).unwrap();
+        let t = solution.time;
+        assert!((circling(t).norm() - 600.0 * t).abs() < 1e-3);
+        assert!((solution.point - circling(t)).norm() < 1e-9);
+
+        // Too slow to catch up
+        assert_eq!(aim_iter(linear, 100.0, 0.0, 100), None);
+    }
+
+    #[test]
+    fn drag() {
+        let target = Target::<f64> {
+            position: na::Vector3::new(4000.0, 500.0, 0.0),
+            velocity: na::Vector3::new(0.0, 300.0, 0.0),
+        };
+        let k = 0.1;
+        let solution = linear_aim_drag(&target, 1000.0, k).unwrap();
+        let t = solution.time;
+        let traveled = 1000.0 / k * (1.0 - (-k * t).exp());
+        assert!((solution.point.norm() - traveled).abs() < 1e-6);
+        assert!((solution.point - (target.position + target.velocity * t)).norm() < 1e-9);
+        let free = linear_aim(&target, 1000.0).unwrap();
+        assert!(t > free.time);
+        // Slowing down, the projectile must lead the target by several degrees more
+        let extra_lead = solution.direction.angle(&free.direction).to_degrees();
+        assert!(extra_lead > 3.0);
+
+        assert_eq!(
+            linear_aim_drag(&target, 1000.0, 0.0),
+            linear_aim(&target, 1000.0)
+        );
+        let stationary = Target::<f64> {
+            position: na::Vector3::new(0.0, 0.0, 5000.0),
+            velocity: na::zero(),
+        };
+        let solution = linear_aim_drag(&stationary, 1000.0, k).unwrap();
+        assert!((1e4 * (1.0 - (-k * solution.time).exp()) - 5000.0).abs() < 1e-9);
+        assert_eq!(linear_aim_drag(&stationary, 1000.0, 0.3), None);
+    }
+
+    #[test]
+    fn refine_ballistic() {
+        let target = Target {
+            position: na::Vector3::new(3000.0, 200.0, 0.0),
+            velocity: na::Vector3::new(-20.0, 0.0, 10.0),
+        };
+        let model = Vacuum {
+            speed: 500.0,
+            gravity: na::Vector3::new(0.0, -9.81, 0.0),
+        };
+        let guess = crate::linear_aim(&target, 500.0).unwrap();
+        let miss = |AimSolution { direction, .. }| {
+            // Closest approach over the whole flight, by brute force
+            (0..20_000)
+                .map(|i| {
+                    let t = i as f64 * 1e-3;
+                    (target.position + target.velocity * t - model.displacement(&direction, t))
+                        .norm()
+                })
+                .fold(f64::INFINITY, f64::min)
+        };
+        let misses = (0..4)
+            .map(|n| miss(refine_aim(&guess, &target, &model, n)))
+            .collect::<Vec<_>>();
+        assert!(misses[0] > 10.0);
+        for pair in misses.windows(2) {
+            assert!(pair[1] < pair[0]);
+        }
+        assert!(misses[3] < 1.0);
+    }
+}
@@ -0,0 +1,215 @@
+//! Vehicle models with constrained maneuvering
+
+use na::RealField;
+
+/// Constant-speed vehicle which can only steer by turning at a limited rate
+#[derive(Debug, Copy, Clone)]
+pub struct TurnRateVehicle<N: RealField> {
+    pub position: na::Point3<N>,
+    pub velocity: na::Vector3<N>,
+    /// Maximum magnitude of the velocity vector's angular rate, in radians per unit time
+    pub max_turn_rate: N,
+}
+
+impl<N: RealField> TurnRateVehicle<N> {
+    /// Advance by `dt`, turning as directed by `lateral_accel`
+    ///
+    /// Any component of `lateral_accel` along the velocity is ignored. The velocity vector is
+    /// rotated at the implied turn rate `|a| / |v|`, capped at `max_turn_rate`, so speed is
+    /// conserved exactly. Returns the turn rate applied.
+    pub fn step(&mut self, lateral_accel: &na::Vector3<N>, dt: N) -> N {
+        let speed = self.velocity.norm();
+        let mut rate = na::zero();
+        if let Some(forward) = self.velocity.try_normalize(N::default_epsilon()) {
+            let lateral = lateral_accel - forward * forward.dot(lateral_accel);
+            if let Some(axis) = na::Unit::try_new(forward.cross(&lateral), N::default_epsilon()) {
+                rate = (lateral.norm() / speed).min(self.max_turn_rate);
+                self.velocity = na::Rotation3::from_axis_angle(&axis, rate * dt) * self.velocity;
+            }
+        }
+        self.position += self.velocity * dt;
+        rate
+    }
+}
+
+/// Constant-speed interceptor whose autopilot tracks guidance commands through a first-order lag
+///
+/// Models the gap between commanded and achieved acceleration that ideal simulations ignore.
+#[derive(Debug, Copy, Clone)]
+pub struct FixedWingInterceptor<N: RealField> {
+    pub position: na::Point3<N>,
+    pub velocity: na::Vector3<N>,
+    /// Autopilot time constant; zero for ideal tracking
+    pub time_constant: N,
+    /// Lateral acceleration currently achieved by the airframe
+    pub achieved: na::Vector3<N>,
+}
+
+impl<N: RealField> FixedWingInterceptor<N> {
+    pub fn new(position: na::Point3<N>, velocity: na::Vector3<N>, time_constant: N) -> Self {
+        Self {
+            position,
+            velocity,
+            time_constant,
+            achieved: na::zero(),
+        }
+    }
+
+    /// Advance by `dt`, with the autopilot tracking guidance `command`
+    ///
+    /// Only the component of `command` perpendicular to the velocity is realizable. The achieved
+    /// acceleration approaches it exponentially, exactly for a command held over the step, and
+    /// rotates the velocity as in `apply_command`, so speed is conserved. Position is integrated
+    /// with the updated velocity. Returns the acceleration applied.
+    pub fn step(&mut self, command: &na::Vector3<N>, dt: N) -> na::Vector3<N> {
+        let lateral = |v: &na::Vector3<N>| match self.velocity.try_normalize(N::default_epsilon()) {
+            Some(forward) => v - forward * forward.dot(v),
+            None => *v,
+        };
+        let demand = lateral(command);
+        let alpha = N::one() - (-dt / self.time_constant).exp();
+        self.achieved = lateral(&(self.achieved + (demand - self.achieved) * alpha));
+        self.velocity = apply_command(&self.velocity, &self.achieved, dt);
+        self.position += self.velocity * dt;
+        self.achieved
+    }
+}
+
+/// Velocity after applying acceleration `command` for `dt`, treating its components separately
+///
+/// The component along the velocity changes speed, and the lateral component rotates the velocity
+/// at rate `|a| / |v|` without changing speed, unlike plain Euler integration, which gains speed
+/// at second order with every lateral step. A zero velocity is integrated directly.
+pub fn apply_command<N: RealField>(
+    velocity: &na::Vector3<N>,
+    command: &na::Vector3<N>,
+    dt: N,
+) -> na::Vector3<N> {
+    let speed = velocity.norm();
+    let forward = match velocity.try_normalize(N::default_epsilon()) {
+        Some(forward) => forward,
+        None => return velocity + command * dt,
+    };
+    let along = forward.dot(command);
+    let lateral = command - forward * along;
+    let new_speed = speed + along * dt;
+    match na::Unit::try_new(forward.cross(&lateral), N::default_epsilon()) {
+        Some(axis) => {
+            let angle = lateral.norm() / speed * dt;
+            na::Rotation3::from_axis_angle(&axis, angle) * forward * new_speed
+        }
+        None => forward * new_speed,
+    }
+}
+
+/// Angular velocity which turns `velocity` toward a lateral acceleration `command`, for
+/// rate-controlled autopilots
+///
+/// Computed as `velocity × command / |velocity|²`, so any component of `command` along the
+/// velocity is ignored. Returns zero if `velocity` is zero.
+pub fn command_to_body_rate<N: RealField>(
+    command: &na::Vector3<N>,
+    velocity: &na::Vector3<N>,
+) -> na::Vector3<N> {
+    let speed_squared = velocity.norm_squared();
+    if speed_squared <= N::default_epsilon() {
+        return na::zero();
+    }
+    velocity.cross(command) / speed_squared
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turn_rate_vehicle() {
+        let mut vehicle = TurnRateVehicle {
+            position: na::Point3::origin(),
+            velocity: na::Vector3::new(250.0f64, 0.0, 0.0),
+            max_turn_rate: 0.3,
+        };
+        let speed = vehicle.velocity.norm();
+        for i in 0..1000 {
+            // Alternate between a gentle and an excessive demand
+            let demand = if i % 2 == 0 { 10.0 } else { 1e4 };
+            let lateral = vehicle.velocity.cross(&na::Vector3::z()).normalize() * demand;
+            let before = vehicle.velocity;
+            let rate = vehicle.step(&lateral, 1e-2);
+            assert!(rate <= vehicle.max_turn_rate);
+            assert!(before.angle(&vehicle.velocity) <= vehicle.max_turn_rate * 1e-2 + 1e-12);
+            assert!((vehicle.velocity.norm() - speed).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn autopilot_lag() {
+        fn miss(time_constant: f64) -> f64 {
+            const TIMESTEP: f64 = 1e-3;
+            let mut missile = FixedWingInterceptor::new(
+                na::Point3::origin(),
+                na::Vector3::new(0.0, 600.0, 0.0),
+                time_constant,
+            );
+            let mut target = (
+                na::Point3::new(800.0, 5000.0, 0.0),
+                na::Vector3::new(0.0, -300.0, 0.0),
+            );
+            let mut closest = f64::INFINITY;
+            loop {
+                let relative = crate::Target {
+                    position: target.0 - missile.position,
+                    velocity: target.1 - missile.velocity,
+                };
+                closest = closest.min(relative.position.norm());
+                if !relative.is_closing() {
+                    return closest;
+                }
+                missile.step(&crate::ipn(3.0, &relative), TIMESTEP);
+                assert!((missile.velocity.norm() - 600.0).abs() < 1e-6);
+                target.0 += target.1 * TIMESTEP;
+            }
+        }
+        let ideal = miss(0.0);
+        let fast = miss(0.2);
+        let slow = miss(1.0);
+        assert!(ideal < 1.0, "{}", ideal);
+        assert!(fast < slow, "{} {}", fast, slow);
+    }
+
+    #[test]
+    fn apply() {
+        let velocity = na::Vector3::new(300.0f64, 0.0, 40.0);
+        let speed = velocity.norm();
+        let lateral = velocity.cross(&na::Vector3::y()).normalize() * 100.0;
+        let mut turning = velocity;
+        let mut euler = velocity;
+        for _ in 0..1000 {
+            let lateral = turning.cross(&na::Vector3::y()).normalize() * 100.0;
+            turning = apply_command(&turning, &lateral, 1e-3);
+            euler += lateral * 1e-3;
+        }
+        assert!((turning.norm() - speed).abs() < 1e-9);
+        assert!(euler.norm() - speed > 1e-3);
+        let turned = apply_command(&velocity, &lateral, 1e-3);
+        assert!((velocity.angle(&turned) - 100.0 / speed * 1e-3).abs() < 1e-12);
+
+        let boosted = apply_command(&velocity, &(velocity.normalize() * 50.0), 0.1);
+        assert!((boosted.norm() - (speed + 5.0)).abs() < 1e-9);
+        assert!(boosted.angle(&velocity) < 1e-9);
+    }
+
+    #[test]
+    fn body_rate() {
+        let velocity = na::Vector3::new(300.0f64, 0.0, 40.0);
+        let command = na::Vector3::new(0.0, 60.0, 0.0);
+        let rate = command_to_body_rate(&command, &velocity);
+        let dt = 1e-3;
+        let turned = na::Rotation3::new(rate * dt) * velocity;
+        let expected = command.norm() / velocity.norm() * dt;
+        assert!((velocity.angle(&turned) - expected).abs() < 1e-12);
+        // The velocity turns toward the commanded direction
+        assert!((turned - velocity).normalize().dot(&command.normalize()) > 1.0 - 1e-6);
+        assert_eq!(command_to_body_rate(&command, &na::zero()), na::zero());
+    }
+}
@@ -0,0 +1,117 @@
+//! Helpers for state estimators tracking a `Target`
+//!
+//! States are `[position, velocity]`, and range/bearing measurements are `[range, bearing,
+//! elevation]` with bearing about the z axis from the x axis and elevation from the xy plane.
+
+use na::RealField;
+
+use crate::Target;
+
+/// State-transition Jacobian of constant-velocity propagation over `dt`
+pub fn cv_transition_jacobian<N: RealField>(dt: N) -> na::Matrix6<N> {
+    let mut jacobian = na::Matrix6::identity();
+    jacobian
+        .fixed_slice_mut::<na::U3, na::U3>(0, 3)
+        .copy_from(&(na::Matrix3::identity() * dt));
+    jacobian
+}
+
+/// Range, bearing, and elevation of `target`
+pub fn range_bearing<N: RealField>(target: &Target<N>) -> na::Vector3<N> {
+    let p = &target.position;
+    na::Vector3::new(
+        p.norm(),
+        p.y.atan2(p.x),
+        p.z.atan2((p.x * p.x + p.y * p.y).sqrt()),
+    )
+}
+
+/// Jacobian of `range_bearing` with respect to the state of `target`
+///
+/// Singular when the target is at the origin or directly above or below it.
+pub fn range_bearing_measurement_jacobian<N: RealField>(target: &Target<N>) -> na::Matrix3x6<N> {
+    let p = &target.position;
+    let horizontal_sq = p.x * p.x + p.y * p.y;
+    let horizontal = horizontal_sq.sqrt();
+    let range_sq = horizontal_sq + p.z * p.z;
+    let range = range_sq.sqrt();
+    let elevation = range_sq * horizontal;
+    let zero = N::zero();
+    #[rustfmt::skip]
+    let jacobian = na::Matrix3x6::new(
+        p.x / range, p.y / range, p.z / range, zero, zero, zero,
+        -p.y / horizontal_sq, p.x / horizontal_sq, zero, zero, zero, zero,
+        -p.x * p.z / elevation, -p.y * p.z / elevation, horizontal / range_sq, zero, zero, zero,
+    );
+    jacobian
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: f64 = 1e-6;
+
+    fn state(target: &Target<f64>) -> na::Vector6<f64> {
+        let mut x = na::Vector6::zeros();
+        x.fixed_rows_mut::<na::U3>(0).copy_from(&target.position);
+        x.fixed_rows_mut::<na::U3>(3).copy_from(&target.velocity);
+        x
+    }
+
+    fn target(x: &na::Vector6<f64>) -> Target<f64> {
+        Target {
+            position: x.fixed_rows::<na::U3>(0).into_owned(),
+            velocity: x.fixed_rows::<na::U3>(3).into_owned(),
+        }
+    }
+
+    /// Central-difference Jacobian of `f` at `x`
+    fn numeric<R: na::DimName>(
+        f: impl Fn(&na::Vector6<f64>) -> na::VectorN<f64, R>,
+        x: &na::Vector6<f64>,
+    ) -> na::MatrixMN<f64, R, na::U6>
+    where
+        na::DefaultAllocator:
+            na::allocator::Allocator<f64, R> + na::allocator::Allocator<f64, R, na::U6>,
+    {
+        let mut jacobian = na::MatrixMN::<f64, R, na::U6>::zeros();
+        for i in 0..6 {
+            let mut hi = *x;
+            hi[i] += EPS;
+            let mut lo = *x;
+            lo[i] -= EPS;
+            jacobian.set_column(i, &((f(&hi) - f(&lo)) / (2.0 * EPS)));
+        }
+        jacobian
+    }
+
+    #[test]
+    fn transition_jacobian() {
+        let dt = 0.25;
+        let x = state(&Target {
+            position: na::Vector3::new(100.0, -20.0, 30.0),
+            velocity: na::Vector3::new(-5.0, 2.0, 1.0),
+        });
+        let propagate = |x: &na::Vector6<f64>| {
+            let t = target(x);
+            state(&Target {
+                position: t.position + t.velocity * dt,
+                velocity: t.velocity,
+            })
+        };
+        let error = cv_transition_jacobian(dt) - numeric(propagate, &x);
+        assert!(error.amax() < 1e-6);
+    }
+
+    #[test]
+    fn measurement_jacobian() {
+        let t = Target {
+            position: na::Vector3::new(100.0, -20.0, 30.0),
+            velocity: na::Vector3::new(-5.0, 2.0, 1.0),
+        };
+        let error = range_bearing_measurement_jacobian(&t)
+            - numeric(|x| range_bearing(&target(x)), &state(&t));
+        assert!(error.amax() < 1e-6);
+    }
+}
@@ -0,0 +1,69 @@
+//! Maneuvers for a target evading a guided pursuer
+
+use na::RealField;
+
+use crate::{los_rate, Target};
+
+/// Direction in which an evader should accelerate to maximize the demand on a pursuer flying
+/// proportional navigation
+///
+/// `threat` is the pursuer relative to the evader. Accelerating perpendicular to the line of sight
+/// in the sense of its existing rotation increases the line-of-sight rate fastest, and with it the
+/// pursuer's command. This is independent of the pursuer's acceleration limit, which only governs
+/// whether the evasion succeeds. On a collision course, an arbitrary perpendicular is chosen.
+pub fn best_evasion_direction<N: RealField>(threat: &Target<N>) -> na::Unit<na::Vector3<N>> {
+    let w_s = los_rate(threat);
+    na::Unit::try_new(threat.position.cross(&w_s), N::default_epsilon()).unwrap_or_else(|| {
+        let reference = if threat.position.x.abs() < threat.position.y.abs() {
+            na::Vector3::x()
+        } else {
+            na::Vector3::y()
+        };
+        na::Unit::new_normalize(threat.position.cross(&reference))
+    })
+}
+
+/// Acceleration of magnitude `max_accel` along `best_evasion_direction`
+pub fn evade<N: RealField>(threat: &Target<N>, max_accel: N) -> na::Vector3<N> {
+    best_evasion_direction(threat).into_inner() * max_accel
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipn;
+
+    /// Peak command of an `ipn` pursuer against an evader steering by `evasion`
+    fn peak_command(evasion: impl Fn(&Target<f64>) -> na::Vector3<f64>) -> f64 {
+        const TIMESTEP: f64 = 1e-3;
+        // Evader relative to the pursuer
+        let mut target = Target {
+            position: na::Vector3::new(200.0, 0.0, 5000.0),
+            velocity: na::Vector3::new(0.0, 0.0, -800.0),
+        };
+        let mut peak = 0.0f64;
+        while target.is_closing() && target.position.norm() > 50.0 {
+            let threat = Target {
+                position: -target.position,
+                velocity: -target.velocity,
+            };
+            let pursuer = ipn(3.0, &target);
+            peak = peak.max(pursuer.norm());
+            target.velocity += TIMESTEP * (evasion(&threat) - pursuer);
+            target.position += TIMESTEP * target.velocity;
+        }
+        peak
+    }
+
+    #[test]
+    fn best_evasion() {
+        let best = peak_command(|threat| evade(threat, 50.0));
+        for &angle in &[0.5, 1.0, 2.0, 3.0] {
+            let alternative = peak_command(|threat| {
+                let axis = na::Unit::new_normalize(threat.position);
+                na::Rotation3::from_axis_angle(&axis, angle) * evade(threat, 50.0)
+            });
+            assert!(best > alternative);
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! Signal conditioning for measurements and commands
+
+use na::RealField;
+
+use crate::Target;
+
+/// Estimates the rate of change of a sequence of guidance commands
+///
+/// Large rates flag oscillation in a guidance loop before it grows into a visible instability.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct CommandDifferentiator<N: RealField> {
+    previous: Option<na::Vector3<N>>,
+}
+
+impl<N: RealField> CommandDifferentiator<N> {
+    pub fn new() -> Self {
+        Self { previous: None }
+    }
+
+    /// Record `command`, issued `dt` after the previous one, and return the backward-difference
+    /// derivative
+    ///
+    /// Returns zero on the first call.
+    pub fn update(&mut self, command: &na::Vector3<N>, dt: N) -> na::Vector3<N> {
+        let rate = match self.previous {
+            Some(previous) if dt > na::zero() => (command - previous) / dt,
+            _ => na::zero(),
+        };
+        self.previous = Some(*command);
+        rate
+    }
+}
+
+/// A filter over a sequence of vector samples
+pub trait VectorFilter<N: RealField> {
+    /// Record `x`, sampled `dt` after the previous sample, and return the filtered value
+    fn filter(&mut self, x: &na::Vector3<N>, dt: N) -> na::Vector3<N>;
+}
+
+/// First-order low-pass filter, for attenuating measurement noise
+///
+/// Exact for signals that are constant over each sample interval, so behavior doesn't depend on
+/// the sampling rate. The first sample initializes the output.
+#[derive(Debug, Copy, Clone)]
+pub struct LowPass<N: RealField> {
+    /// Corner frequency, in radians per unit time
+    pub cutoff: N,
+    state: Option<na::Vector3<N>>,
+}
+
+impl<N: RealField> LowPass<N> {
+    pub fn new(cutoff: N) -> Self {
+        Self {
+            cutoff,
+            state: None,
+        }
+    }
+}
+
+impl<N: RealField> VectorFilter<N> for LowPass<N> {
+    fn filter(&mut self, x: &na::Vector3<N>, dt: N) -> na::Vector3<N> {
+        let output = match self.state {
+            None => *x,
+            Some(previous) => {
+                let alpha = N::one() - (-self.cutoff * dt).exp();
+                previous + (x - previous) * alpha
+            }
+        };
+        self.state = Some(output);
+        output
+    }
+}
+
+/// First-order high-pass filter, for extracting changes in a signal such as LOS rate
+///
+/// The exact complement of a `LowPass` with the same cutoff: the two outputs always sum to the
+/// input.
+#[derive(Debug, Copy, Clone)]
+pub struct HighPass<N: RealField> {
+    low: LowPass<N>,
+}
+
+impl<N: RealField> HighPass<N> {
+    pub fn new(cutoff: N) -> Self {
+        Self {
+            low: LowPass::new(cutoff),
+        }
+    }
+
+    /// Corner frequency, in radians per unit time
+    pub fn cutoff(&self) -> N {
+        self.low.cutoff
+    }
+}
+
+impl<N: RealField> VectorFilter<N> for HighPass<N> {
+    fn filter(&mut self, x: &na::Vector3<N>, dt: N) -> na::Vector3<N> {
+        x - self.low.filter(x, dt)
+    }
+}
+
+/// Alpha-beta tracker estimating a target's relative position and velocity from noisy position
+/// measurements
+///
+/// Each update predicts the state forward at constant velocity, then corrects the position by
+/// `alpha` and the velocity by `beta / dt` times the residual. Smaller gains reject more noise but
+/// follow maneuvers more slowly. The first measurement initializes the position, with zero
+/// velocity.
+#[derive(Debug, Copy, Clone)]
+pub struct LosFilter<N: RealField> {
+    pub alpha: N,
+    pub beta: N,
+    state: Option<Target<N>>,
+}
+
+impl<N: RealField> LosFilter<N> {
+    pub fn new(alpha: N, beta: N) -> Self {
+        Self {
+            alpha,
+            beta,
+            state: None,
+        }
+    }
+
+    /// Record a position measured `dt` after the previous one, and return the filtered estimate
+    pub fn update(&mut self, measured_position: na::Vector3<N>, dt: N) -> Target<N> {
+        let estimate = match self.state {
+            None => Target {
+                position: measured_position,
+                velocity: na::zero(),
+            },
+            Some(previous) => {
+                let predicted = previous.predict(dt);
+                let residual = measured_position - predicted.position;
+                Target {
+                    position: predicted.position + residual * self.alpha,
+                    velocity: predicted.velocity + residual * (self.beta / dt),
+                }
+            }
+        };
+        self.state = Some(estimate);
+        estimate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn differentiate() {
+        let dt = 1e-3;
+        // Magnitude 50, rotating at 2 rad/s
+        let mut rotating = CommandDifferentiator::new();
+        let mut constant = CommandDifferentiator::new();
+        assert_eq!(rotating.update(&(na::Vector3::x() * 50.0), dt), na::zero());
+        for i in 1..1000 {
+            let angle = 2.0 * i as f64 * dt;
+            let command = na::Vector3::new(angle.cos(), angle.sin(), 0.0) * 50.0;
+            let rate = rotating.update(&command, dt);
+            assert!((rate.norm() - 100.0).abs() < 1e-3);
+            let steady = constant.update(&na::Vector3::new(3.0, -1.0, 9.81), dt);
+            assert_eq!(steady, na::zero());
+        }
+    }
+
+    #[test]
+    fn dc_gain() {
+        let dc = na::Vector3::new(3.0, -1.0, 9.81);
+        let mut low = LowPass::new(5.0);
+        let mut high = HighPass::new(5.0);
+        // Start from rest so the filters must settle
+        low.filter(&na::zero(), 1e-2);
+        high.filter(&na::zero(), 1e-2);
+        for _ in 0..1000 {
+            low.filter(&dc, 1e-2);
+            high.filter(&dc, 1e-2);
+        }
+        assert!((low.filter(&dc, 1e-2) - dc).norm() < 1e-9);
+        assert!(high.filter(&dc, 1e-2).norm() < 1e-9);
+    }
+
+    #[test]
+    fn complementary() {
+        let mut low = LowPass::new(2.0);
+        let mut high = HighPass::new(2.0);
+        for i in 0..500 {
+            let t = i as f64 * 1e-2;
+            let x = na::Vector3::new((7.0 * t).sin(), t, (0.5 * t).cos());
+            let sum = low.filter(&x, 1e-2) + high.filter(&x, 1e-2);
+            assert!((sum - x).norm() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn los_filter() {
+        let truth = Target {
+            position: na::Vector3::new(200.0, 8000.0, -100.0),
+            velocity: na::Vector3::new(-30.0, -450.0, 10.0),
+        };
+        let dt = 0.05;
+        let mut filter = LosFilter::new(0.3, 0.02);
+        // Deterministic noise of up to 10 each axis
+        let mut seed = 1u32;
+        let mut noise = || {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (f64::from(seed >> 8) / f64::from(1u32 << 24) - 0.5) * 20.0
+        };
+        let mut error = 0.0;
+        for i in 0..400 {
+            let position = truth.predict(i as f64 * dt).position;
+            let measured = position + na::Vector3::new(noise(), noise(), noise());
+            let estimate = filter.update(measured, dt);
+            error = (estimate.velocity - truth.velocity).norm();
+        }
+        // Differencing raw measurements would be off by hundreds
+        assert!(error < 10.0);
+    }
+}
@@ -0,0 +1,237 @@
+//! Reference frame conversions
+
+use na::RealField;
+
+use crate::Target;
+
+/// A reference frame translating uniformly relative to the inertial frame, e.g. a moving launcher
+///
+/// Accelerations, and hence guidance commands, are identical in both frames and may be applied
+/// without conversion. Positions and velocities are not, and must be converted as below.
+#[derive(Debug, Copy, Clone)]
+pub struct MovingFrame<N: RealField> {
+    /// Inertial position of the frame's origin at time zero
+    pub origin: na::Point3<N>,
+    /// Inertial velocity of the frame
+    pub velocity: na::Vector3<N>,
+}
+
+impl<N: RealField> MovingFrame<N> {
+    /// Inertial position of the frame's origin at `time`
+    #[inline]
+    pub fn origin_at(&self, time: N) -> na::Point3<N> {
+        self.origin + self.velocity * time
+    }
+
+    /// Express an inertial position at `time` in this frame
+    #[inline]
+    pub fn point_to_local(&self, point: &na::Point3<N>, time: N) -> na::Point3<N> {
+        na::Point3::from(point - self.origin_at(time))
+    }
+
+    /// Express a position in this frame at `time` inertially
+    #[inline]
+    pub fn point_to_inertial(&self, point: &na::Point3<N>, time: N) -> na::Point3<N> {
+        self.origin_at(time) + point.coords
+    }
+
+    /// Express an inertial velocity in this frame
+    #[inline]
+    pub fn velocity_to_local(&self, velocity: &na::Vector3<N>) -> na::Vector3<N> {
+        velocity - self.velocity
+    }
+
+    /// Express a velocity in this frame inertially
+    #[inline]
+    pub fn velocity_to_inertial(&self, velocity: &na::Vector3<N>) -> na::Vector3<N> {
+        velocity + self.velocity
+    }
+
+    /// A target with the given inertial state at `time`, relative to the frame's origin
+    ///
+    /// This is the form expected by e.g. `linear_aim` for projectiles whose speed is given
+    /// relative to the frame.
+    pub fn target(
+        &self,
+        position: &na::Point3<N>,
+        velocity: &na::Vector3<N>,
+        time: N,
+    ) -> Target<N> {
+        Target {
+            position: self.point_to_local(position, time).coords,
+            velocity: self.velocity_to_local(velocity),
+        }
+    }
+}
+
+/// Rotation into a frame aligned with the line of sight to `target`, and `target` resolved in it
+///
+/// The x axis lies along the line of sight, and the z axis along its angular velocity, so the
+/// target's position is `(r, 0, 0)` and its velocity is `(dr/dt, r * |w_s|, 0)`, exposing range
+/// rate and LOS rate separately. The axes rotate with the line of sight, but the velocity is the
+/// relative velocity resolved along them, excluding the frame's own rotation. If the line of sight
+/// isn't rotating, the z axis is an arbitrary perpendicular.
+pub fn to_los_frame<N: RealField>(target: &Target<N>) -> (na::Rotation3<N>, Target<N>) {
+    let x = target
+        .position
+        .try_normalize(N::default_epsilon())
+        .unwrap_or_else(na::Vector3::x);
+    let z = target
+        .angular_momentum()
+        .try_normalize(N::default_epsilon())
+        .unwrap_or_else(|| {
+            let reference = if x.x.abs() < na::convert(0.9) {
+                na::Vector3::x()
+            } else {
+                na::Vector3::y()
+            };
+            x.cross(&reference).normalize()
+        });
+    let y = z.cross(&x);
+    let rotation = na::Rotation3::from_matrix_unchecked(na::Matrix3::from_rows(&[
+        x.transpose(),
+        y.transpose(),
+        z.transpose(),
+    ]));
+    let local = Target {
+        position: rotation * target.position,
+        velocity: rotation * target.velocity,
+    };
+    (rotation, local)
+}
+
+/// Orientation pointing a vehicle's forward axis along `current_velocity + command_accel`
+///
+/// The forward axis is local +z and the local +y axis is turned as near to `up` as possible,
+/// resolving roll, as in `na::UnitQuaternion::face_towards`. Without a command the vehicle faces
+/// along its velocity, and without either along `up`. Should the forward direction be parallel to
+/// `up`, an arbitrary perpendicular is used instead.
+pub fn desired_attitude<N: RealField>(
+    current_velocity: &na::Vector3<N>,
+    command_accel: &na::Vector3<N>,
+    up: &na::Unit<na::Vector3<N>>,
+) -> na::UnitQuaternion<N> {
+    let eps = N::default_epsilon();
+    let forward = (current_velocity + command_accel)
+        .try_normalize(eps)
+        .or_else(|| current_velocity.try_normalize(eps))
+        .unwrap_or_else(|| up.into_inner());
+    let up = if forward.cross(up).norm() > eps.sqrt() {
+        up.into_inner()
+    } else if forward.x.abs() < na::convert(0.9) {
+        na::Vector3::x()
+    } else {
+        na::Vector3::y()
+    };
+    na::UnitQuaternion::face_towards(&forward, &up)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipn;
+
+    /// Fly a missile against a target with `ipn`, returning its position and the time at closest
+    /// approach
+    fn engage(
+        mut missile: (na::Point3<f64>, na::Vector3<f64>),
+        mut target: (na::Point3<f64>, na::Vector3<f64>),
+    ) -> (na::Point3<f64>, f64) {
+        const TIMESTEP: f64 = 1e-3;
+        let mut time = 0.0;
+        loop {
+            let relative = Target {
+                position: target.0 - missile.0,
+                velocity: target.1 - missile.1,
+            };
+            if !relative.is_closing() {
+                return (missile.0, time);
+            }
+            missile.1 += TIMESTEP * -ipn(3.0, &relative);
+            missile.0 += TIMESTEP * missile.1;
+            target.0 += TIMESTEP * target.1;
+            time += TIMESTEP;
+        }
+    }
+
+    #[test]
+    fn moving_frame_intercept() {
+        let launcher = MovingFrame {
+            origin: na::Point3::new(100.0, 0.0, 0.0),
+            velocity: na::Vector3::new(0.0, 40.0, 0.0),
+        };
+        let missile = (
+            launcher.origin,
+            launcher.velocity_to_inertial(&na::Vector3::new(300.0, 0.0, 0.0)),
+        );
+        let target = (
+            na::Point3::new(3000.0, 1000.0, 200.0),
+            na::Vector3::new(-50.0, -80.0, 0.0),
+        );
+
+        let (inertial, inertial_time) = engage(missile, target);
+        let (local, local_time) = engage(
+            (
+                launcher.point_to_local(&missile.0, 0.0),
+                launcher.velocity_to_local(&missile.1),
+            ),
+            (
+                launcher.point_to_local(&target.0, 0.0),
+                launcher.velocity_to_local(&target.1),
+            ),
+        );
+        assert!((inertial_time - local_time).abs() < 2e-3);
+        assert!(na::distance(&inertial, &launcher.point_to_inertial(&local, local_time)) < 1e-6);
+
+        let relative = launcher.target(&target.0, &target.1, 0.0);
+        assert_eq!(relative.position, target.0 - launcher.origin);
+        assert_eq!(relative.velocity, na::Vector3::new(-50.0, -120.0, 0.0));
+    }
+
+    #[test]
+    fn los_frame() {
+        let target = Target::<f64> {
+            position: na::Vector3::new(3000.0, 1000.0, 200.0),
+            velocity: na::Vector3::new(-500.0, 80.0, 40.0),
+        };
+        let (rotation, local) = to_los_frame(&target);
+        let range = target.position.norm();
+        assert!((local.position - na::Vector3::x() * range).norm() < 1e-9);
+        let range_rate = target.position.dot(&target.velocity) / range;
+        let los_rate = target.angular_momentum().norm() / (range * range);
+        let expected = na::Vector3::new(range_rate, range * los_rate, 0.0);
+        assert!((local.velocity - expected).norm() < 1e-9);
+        assert!((rotation.inverse() * local.velocity - target.velocity).norm() < 1e-9);
+
+        // Without LOS rotation, the position is still along the axis
+        let head_on = Target::<f64> {
+            position: na::Vector3::new(0.0, 0.0, 10.0),
+            velocity: na::Vector3::new(0.0, 0.0, -1.0),
+        };
+        let (rotation, local) = to_los_frame(&head_on);
+        assert!((local.position - na::Vector3::x() * 10.0).norm() < 1e-9);
+        assert!((local.velocity + na::Vector3::x()).norm() < 1e-9);
+        assert!((rotation.matrix().determinant() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn attitude() {
+        let up = na::Vector3::z_axis();
+        let velocity = na::Vector3::new(300.0f64, 0.0, 0.0);
+        let command = na::Vector3::new(0.0, 40.0, 10.0);
+        let attitude = desired_attitude(&velocity, &command, &up);
+        let intended = (velocity + command).normalize();
+        assert!((attitude * na::Vector3::z() - intended).norm() < 1e-9);
+        // Rolled upright
+        assert!((attitude * na::Vector3::y()).dot(&up) > 0.9);
+
+        let coasting = desired_attitude(&velocity, &na::zero(), &up);
+        assert!((coasting * na::Vector3::z() - na::Vector3::x()).norm() < 1e-9);
+        let climbing = desired_attitude(&na::Vector3::new(0.0, 0.0, 300.0), &na::zero(), &up);
+        assert!((climbing * na::Vector3::z() - na::Vector3::z()).norm() < 1e-9);
+        let cancelled = desired_attitude(&velocity, &-velocity, &up);
+        assert!((cancelled * na::Vector3::z() - na::Vector3::x()).norm() < 1e-9);
+        let still = desired_attitude(&na::zero(), &na::zero(), &up);
+        assert!(still.coords.iter().all(|x| x.is_finite()));
+    }
+}
@@ -0,0 +1,92 @@
+//! Warhead fuzing
+
+use na::RealField;
+
+use crate::{linear_aim, Target};
+
+/// Detonates at closest approach, if that is within `radius`
+#[derive(Debug, Copy, Clone)]
+pub struct ProximityFuze<N: RealField> {
+    pub radius: N,
+}
+
+impl<N: RealField> ProximityFuze<N> {
+    /// Whether to detonate during the next `dt`, assuming constant relative velocity
+    ///
+    /// Returns the miss distance at the moment of detonation.
+    pub fn check(&self, target: &Target<N>, dt: N) -> Option<N> {
+        let speed_squared = target.velocity.norm_squared();
+        if speed_squared <= N::default_epsilon() {
+            return None;
+        }
+        let t = -target.position.dot(&target.velocity) / speed_squared;
+        if t < na::zero() || t > dt {
+            return None;
+        }
+        let miss = (target.position + target.velocity * t).norm();
+        if miss <= self.radius {
+            Some(miss)
+        } else {
+            None
+        }
+    }
+}
+
+/// Direction to orient a directional warhead so that fragments ejected at `fragment_speed`
+/// relative to the missile strike `target`
+///
+/// The fragments inherit the missile's velocity, so in the missile's frame this is `linear_aim`
+/// against the relative motion at burst. Falls back to the line of sight if the fragments can't
+/// reach the target.
+pub fn fragment_aim<N: RealField>(
+    target: &Target<N>,
+    fragment_speed: N,
+) -> na::Unit<na::Vector3<N>> {
+    linear_aim(target, fragment_speed).map_or_else(
+        || na::Unit::new_normalize(target.position),
+        |solution| solution.direction,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proximity() {
+        let fuze = ProximityFuze { radius: 5.0 };
+        let target = |z: f64| Target {
+            position: na::Vector3::new(3.0, 0.0, z),
+            velocity: na::Vector3::new(0.0, 0.0, -100.0),
+        };
+        // Closest approach too far in the future, then within the step, then already past
+        assert_eq!(fuze.check(&target(50.0), 0.1), None);
+        assert_eq!(fuze.check(&target(5.0), 0.1), Some(3.0));
+        assert_eq!(fuze.check(&target(-1.0), 0.1), None);
+        let wide = Target {
+            position: na::Vector3::new(30.0, 0.0, 5.0),
+            ..target(5.0)
+        };
+        assert_eq!(fuze.check(&wide, 0.1), None);
+    }
+
+    #[test]
+    fn fragments() {
+        let head_on = Target {
+            position: na::Vector3::new(20.0, 0.0, 0.0),
+            velocity: na::Vector3::new(-1000.0, 0.0, 0.0),
+        };
+        let forward = fragment_aim(&head_on, 1500.0);
+        assert!((forward.into_inner() - na::Vector3::x()).norm() < 1e-9);
+
+        let crossing = Target::<f64> {
+            position: na::Vector3::new(20.0, 0.0, 0.0),
+            velocity: na::Vector3::new(-800.0, 600.0, 0.0),
+        };
+        let lead = fragment_aim(&crossing, 1500.0);
+        assert!(lead.y > 0.0);
+        let t = linear_aim(&crossing, 1500.0).unwrap().time;
+        let fragment = lead.into_inner() * 1500.0 * t;
+        assert!((fragment - (crossing.position + crossing.velocity * t)).norm() < 1e-9);
+    }
+}
@@ -0,0 +1,646 @@
+//! Guidance laws as composable values
+
+use na::RealField;
+
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
+
+use crate::{
+    clamp_acceleration, ipn, linear_steer, pure_pursuit, tpn, Acquisition, AcquisitionState,
+    LosFilter, Target, VectorFilter,
+};
+
+/// A guidance law, producing acceleration commands from the engagement geometry
+///
+/// Laws may carry state between calls, so wrappers can filter, limit, or otherwise adapt an inner
+/// law's output.
+pub trait GuidanceLaw<N: RealField> {
+    /// Acceleration to apply over the next `dt`, given the relative `target` and the guided
+    /// vehicle's own `current_velocity`
+    fn command(
+        &mut self,
+        target: &Target<N>,
+        current_velocity: &na::Vector3<N>,
+        dt: N,
+    ) -> na::Vector3<N>;
+}
+
+impl<N: RealField, L: GuidanceLaw<N> + ?Sized> GuidanceLaw<N> for &mut L {
+    fn command(
+        &mut self,
+        target: &Target<N>,
+        current_velocity: &na::Vector3<N>,
+        dt: N,
+    ) -> na::Vector3<N> {
+        (**self).command(target, current_velocity, dt)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N: RealField, L: GuidanceLaw<N> + ?Sized> GuidanceLaw<N> for Box<L> {
+    fn command(
+        &mut self,
+        target: &Target<N>,
+        current_velocity: &na::Vector3<N>,
+        dt: N,
+    ) -> na::Vector3<N> {
+        (**self).command(target, current_velocity, dt)
+    }
+}
+
+/// Ideal proportional navigation, commanding zero acceleration when the target isn't closing
+///
+/// See `ipn`.
+#[derive(Debug, Copy, Clone)]
+pub struct Ipn<N: RealField> {
+    pub navigation_constant: N,
+}
+
+impl<N: RealField> GuidanceLaw<N> for Ipn<N> {
+    fn command(&mut self, target: &Target<N>, _: &na::Vector3<N>, _: N) -> na::Vector3<N> {
+        if target.is_closing() {
+            ipn(self.navigation_constant, target)
+        } else {
+            na::zero()
+        }
+    }
+}
+
+/// True proportional navigation, commanding zero acceleration when the target isn't closing
+///
+/// See `tpn`.
+#[derive(Debug, Copy, Clone)]
+pub struct Tpn<N: RealField> {
+    pub navigation_constant: N,
+}
+
+impl<N: RealField> GuidanceLaw<N> for Tpn<N> {
+    fn command(&mut self, target: &Target<N>, _: &na::Vector3<N>, _: N) -> na::Vector3<N> {
+        if target.is_closing() {
+            tpn(self.navigation_constant, target)
+        } else {
+            na::zero()
+        }
+    }
+}
+
+/// Pure pursuit
+///
+/// See `pure_pursuit`.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct PurePursuit;
+
+impl<N: RealField> GuidanceLaw<N> for PurePursuit {
+    fn command(
+        &mut self,
+        target: &Target<N>,
+        current_velocity: &na::Vector3<N>,
+        _: N,
+    ) -> na::Vector3<N> {
+        pure_pursuit(target, current_velocity)
+    }
+}
+
+/// Steers onto a linear intercept course within a single step, commanding zero acceleration when
+/// the target isn't closing or can't be reached
+///
+/// See `linear_steer`. The steering change in velocity is spread over `dt`, so the command is
+/// typically large and should be clamped, e.g. by `EngagementState::max_accel`.
+#[derive(Debug, Copy, Clone)]
+pub struct LinearSteer<N: RealField> {
+    pub average_speed: N,
+}
+
+impl<N: RealField> GuidanceLaw<N> for LinearSteer<N> {
+    fn command(
+        &mut self,
+        target: &Target<N>,
+        current_velocity: &na::Vector3<N>,
+        dt: N,
+    ) -> na::Vector3<N> {
+        if !target.is_closing() {
+            return na::zero();
+        }
+        match linear_steer(target, current_velocity, self.average_speed) {
+            Some((delta_v, _)) => delta_v / dt,
+            None => na::zero(),
+        }
+    }
+}
+
+/// Adds gravity and drag cancellation feedforward to the output of `law`
+#[derive(Debug, Copy, Clone)]
+pub struct Compensated<N: RealField, L> {
+    pub law: L,
+    /// Gravitational acceleration acting on the vehicle
+    pub gravity: na::Vector3<N>,
+    /// Quadratic drag coefficient per unit mass, such that drag decelerates by `drag * |v| * v`
+    pub drag: N,
+}
+
+impl<N: RealField, L: GuidanceLaw<N>> GuidanceLaw<N> for Compensated<N, L> {
+    fn command(
+        &mut self,
+        target: &Target<N>,
+        current_velocity: &na::Vector3<N>,
+        dt: N,
+    ) -> na::Vector3<N> {
+        self.law.command(target, current_velocity, dt) - self.gravity
+            + current_velocity * (self.drag * current_velocity.norm())
+    }
+}
+
+/// Runs `law` at a reduced rate, holding its last command between updates
+///
+/// Useful when guidance is too expensive to run at the full simulation rate. The inner law is
+/// passed the time elapsed since its previous update as `dt`.
+#[derive(Debug, Clone)]
+pub struct RateLimiter<N: RealField, L> {
+    pub law: L,
+    period: N,
+    elapsed: N,
+    last: Option<na::Vector3<N>>,
+}
+
+impl<N: RealField, L> RateLimiter<N, L> {
+    /// Invoke `law` at most `rate` times per unit time
+    pub fn new(law: L, rate: N) -> Self {
+        Self {
+            law,
+            period: N::one() / rate,
+            elapsed: na::zero(),
+            last: None,
+        }
+    }
+}
+
+impl<N: RealField, L: GuidanceLaw<N>> GuidanceLaw<N> for RateLimiter<N, L> {
+    fn command(
+        &mut self,
+        target: &Target<N>,
+        current_velocity: &na::Vector3<N>,
+        dt: N,
+    ) -> na::Vector3<N> {
+        self.elapsed += dt;
+        // Tolerate rounding in the accumulated time
+        let due = self.elapsed >= self.period * na::convert(1.0 - 1e-6);
+        match self.last {
+            Some(last) if !due => last,
+            _ => {
+                let command = self.law.command(target, current_velocity, self.elapsed);
+                self.elapsed = na::zero();
+                self.last = Some(command);
+                command
+            }
+        }
+    }
+}
+
+/// Smooths the command transient from a sudden jump in the target's estimated velocity
+///
+/// When a track is reinitialized, e.g. after a dropout, the velocity estimate can change
+/// discontinuously and an undamped law responds with a spike. If the velocity changes by more
+/// than `threshold` between calls, the output instead ramps linearly from its previous value
+/// towards `law`'s command over `ramp_time`.
+#[derive(Debug, Clone)]
+pub struct TransientSuppressor<N: RealField, L> {
+    pub law: L,
+    pub threshold: N,
+    pub ramp_time: N,
+    previous_velocity: Option<na::Vector3<N>>,
+    last: na::Vector3<N>,
+    ramp: Option<(na::Vector3<N>, N)>,
+}
+
+impl<N: RealField, L> TransientSuppressor<N, L> {
+    pub fn new(law: L, threshold: N, ramp_time: N) -> Self {
+        Self {
+            law,
+            threshold,
+            ramp_time,
+            previous_velocity: None,
+            last: na::zero(),
+            ramp: None,
+        }
+    }
+}
+
+impl<N: RealField, L: GuidanceLaw<N>> GuidanceLaw<N> for TransientSuppressor<N, L> {
+    fn command(
+        &mut self,
+        target: &Target<N>,
+        current_velocity: &na::Vector3<N>,
+        dt: N,
+    ) -> na::Vector3<N> {
+        let command = self.law.command(target, current_velocity, dt);
+        if let Some(previous) = self.previous_velocity {
+            if (target.velocity - previous).norm() > self.threshold {
+                self.ramp = Some((self.last, na::zero()));
+            }
+        }
+        self.previous_velocity = Some(target.velocity);
+        let output = match self.ramp {
+            Some((from, elapsed)) => {
+                let elapsed = elapsed + dt;
+                let fraction = elapsed / self.ramp_time;
+                if fraction >= N::one() {
+                    self.ramp = None;
+                    command
+                } else {
+                    self.ramp = Some((from, elapsed));
+                    from.lerp(&command, fraction)
+                }
+            }
+            None => command,
+        };
+        self.last = output;
+        output
+    }
+}
+
+/// Limits the magnitude of `law`'s command to `max_accel`
+///
+/// See `clamp_acceleration`.
+#[derive(Debug, Copy, Clone)]
+pub struct AccelerationLimit<N: RealField, L> {
+    pub law: L,
+    pub max_accel: N,
+}
+
+impl<N: RealField, L: GuidanceLaw<N>> GuidanceLaw<N> for AccelerationLimit<N, L> {
+    fn command(
+        &mut self,
+        target: &Target<N>,
+        current_velocity: &na::Vector3<N>,
+        dt: N,
+    ) -> na::Vector3<N> {
+        let command = self.law.command(target, current_velocity, dt);
+        clamp_acceleration(command, self.max_accel)
+    }
+}
+
+/// Passes `law`'s command through `filter`, e.g. a `LowPass` to attenuate chatter
+#[derive(Debug, Copy, Clone)]
+pub struct Filtered<L, F> {
+    pub law: L,
+    pub filter: F,
+}
+
+impl<N: RealField, L: GuidanceLaw<N>, F: VectorFilter<N>> GuidanceLaw<N> for Filtered<L, F> {
+    fn command(
+        &mut self,
+        target: &Target<N>,
+        current_velocity: &na::Vector3<N>,
+        dt: N,
+    ) -> na::Vector3<N> {
+        let command = self.law.command(target, current_velocity, dt);
+        self.filter.filter(&command, dt)
+    }
+}
+
+/// Guides `law` by `filter`'s estimate of the target, formed from its measured position alone
+///
+/// Models a seeker which measures the line of sight and range but not their rates.
+#[derive(Debug, Copy, Clone)]
+pub struct Tracked<N: RealField, L> {
+    pub law: L,
+    pub filter: LosFilter<N>,
+}
+
+impl<N: RealField, L: GuidanceLaw<N>> GuidanceLaw<N> for Tracked<N, L> {
+    fn command(
+        &mut self,
+        target: &Target<N>,
+        current_velocity: &na::Vector3<N>,
+        dt: N,
+    ) -> na::Vector3<N> {
+        let estimate = self.filter.update(target.position, dt);
+        self.law.command(&estimate, current_velocity, dt)
+    }
+}
+
+/// Delays `law`'s commands by `delay`, modeling processing and actuation latency
+///
+/// Each command takes effect once `delay` has elapsed since it was computed; until the first one
+/// does, zero is commanded.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct Latency<N: RealField, L> {
+    pub law: L,
+    pub delay: N,
+    elapsed: N,
+    pending: VecDeque<(N, na::Vector3<N>)>,
+    current: na::Vector3<N>,
+}
+
+#[cfg(feature = "std")]
+impl<N: RealField, L> Latency<N, L> {
+    pub fn new(law: L, delay: N) -> Self {
+        Self {
+            law,
+            delay,
+            elapsed: na::zero(),
+            pending: VecDeque::new(),
+            current: na::zero(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N: RealField, L: GuidanceLaw<N>> GuidanceLaw<N> for Latency<N, L> {
+    fn command(
+        &mut self,
+        target: &Target<N>,
+        current_velocity: &na::Vector3<N>,
+        dt: N,
+    ) -> na::Vector3<N> {
+        let command = self.law.command(target, current_velocity, dt);
+        self.pending.push_back((self.elapsed, command));
+        // Tolerate rounding in the accumulated time
+        let delay = self.delay * na::convert(1.0 - 1e-6);
+        while let Some(&(issued, command)) = self.pending.front() {
+            if self.elapsed - issued < delay {
+                break;
+            }
+            self.current = command;
+            self.pending.pop_front();
+        }
+        self.elapsed += dt;
+        self.current
+    }
+}
+
+/// Weaves from side to side to sweep a forward-looking seeker across the sky
+///
+/// Ignores the target, commanding acceleration of magnitude up to `accel` perpendicular to both
+/// the velocity and `up`, alternating sides every half `period`.
+#[derive(Debug, Copy, Clone)]
+pub struct WeaveSearch<N: RealField> {
+    pub accel: N,
+    pub period: N,
+    pub up: na::Unit<na::Vector3<N>>,
+    elapsed: N,
+}
+
+impl<N: RealField> WeaveSearch<N> {
+    pub fn new(accel: N, period: N, up: na::Unit<na::Vector3<N>>) -> Self {
+        Self {
+            accel,
+            period,
+            up,
+            elapsed: na::zero(),
+        }
+    }
+}
+
+impl<N: RealField> GuidanceLaw<N> for WeaveSearch<N> {
+    fn command(
+        &mut self,
+        _: &Target<N>,
+        current_velocity: &na::Vector3<N>,
+        dt: N,
+    ) -> na::Vector3<N> {
+        self.elapsed += dt;
+        let side = match na::Unit::try_new(current_velocity.cross(&self.up), N::default_epsilon()) {
+            Some(side) => side,
+            None => return na::zero(),
+        };
+        side.into_inner() * (self.accel * (N::two_pi() * self.elapsed / self.period).sin())
+    }
+}
+
+/// Flies `search` until `acquisition` locks on to the target, then guides with `law`
+///
+/// The seeker is assumed to look along the vehicle's velocity. Should lock be lost, the search
+/// resumes.
+#[derive(Debug, Copy, Clone)]
+pub struct SearchAndHome<N: RealField, S, L> {
+    pub acquisition: Acquisition<N>,
+    pub search: S,
+    pub law: L,
+}
+
+impl<N: RealField, S: GuidanceLaw<N>, L: GuidanceLaw<N>> GuidanceLaw<N> for SearchAndHome<N, S, L> {
+    fn command(
+        &mut self,
+        target: &Target<N>,
+        current_velocity: &na::Vector3<N>,
+        dt: N,
+    ) -> na::Vector3<N> {
+        let boresight = match na::Unit::try_new(*current_velocity, N::default_epsilon()) {
+            Some(boresight) => boresight,
+            None => return self.search.command(target, current_velocity, dt),
+        };
+        match self.acquisition.update(target, &boresight, dt) {
+            AcquisitionState::Locked => self.law.command(target, current_velocity, dt),
+            _ => self.search.command(target, current_velocity, dt),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts invocations, commanding a distinct acceleration each time
+    struct Counting(usize);
+
+    impl GuidanceLaw<f64> for Counting {
+        fn command(&mut self, _: &Target<f64>, _: &na::Vector3<f64>, _: f64) -> na::Vector3<f64> {
+            self.0 += 1;
+            na::Vector3::x() * self.0 as f64
+        }
+    }
+
+    #[test]
+    fn compensated() {
+        let target = Target {
+            position: na::Vector3::new(100.0f64, 2000.0, 50.0),
+            velocity: na::Vector3::new(-30.0, -400.0, 0.0),
+        };
+        let velocity = na::Vector3::new(0.0, 300.0, 40.0);
+        let mut law = Compensated {
+            law: Ipn {
+                navigation_constant: 4.0,
+            },
+            gravity: na::Vector3::new(0.0, 0.0, -9.81),
+            drag: 1e-4,
+        };
+        let expected = ipn(4.0, &target)
+            + na::Vector3::new(0.0, 0.0, 9.81)
+            + velocity * (1e-4 * velocity.norm());
+        let actual = law.command(&target, &velocity, 0.01);
+        assert!((actual - expected).norm() < 1e-12);
+    }
+
+    #[test]
+    fn compare_laws() {
+        use crate::{Body, EngagementState, Simulation};
+
+        let state = EngagementState::new(
+            Body {
+                position: na::Point3::origin(),
+                velocity: na::Vector3::new(0.0, 600.0, 0.0),
+            },
+            Body {
+                position: na::Point3::new(-3000.0, 6000.0, 0.0),
+                velocity: na::Vector3::new(250.0, 0.0, 0.0),
+            },
+            400.0,
+        );
+        fn run<L: GuidanceLaw<f64>>(state: EngagementState<f64>, law: L) -> (f64, f64) {
+            let mut sim = Simulation::new(state, law, 1e-3);
+            while !sim.step() {}
+            (sim.miss(), sim.peak_accel())
+        }
+        let laws: Vec<Box<dyn GuidanceLaw<f64>>> = vec![
+            Box::new(Ipn {
+                navigation_constant: 3.0,
+            }),
+            Box::new(Tpn {
+                navigation_constant: 3.0,
+            }),
+            Box::new(PurePursuit),
+        ];
+        let results = laws
+            .into_iter()
+            .map(|law| run(state, law))
+            .collect::<Vec<_>>();
+        for &(miss, _) in &results[..2] {
+            assert!(miss < 1.0);
+        }
+        // Pursuit ends in a tail chase, saturating and falling behind
+        assert!(results[2].0 > 10.0);
+        assert!(results[2].1 > 10.0 * results[0].1.max(results[1].1));
+    }
+
+    #[test]
+    fn linear_steer_law() {
+        // The scene from the `anim` example
+        let target = Target {
+            position: na::Vector3::new(1e4f64, 3e3, 0.0),
+            velocity: na::Vector3::new(-2e3, -1e3, 0.0),
+        };
+        let velocity = na::Vector3::new(0.0, 1e3, 0.0);
+        let dt = 1.0 / 300.0;
+        let mut law = LinearSteer {
+            average_speed: velocity.norm(),
+        };
+        let expected = linear_steer(&target, &velocity, velocity.norm()).unwrap().0 / dt;
+        assert_eq!(law.command(&target, &velocity, dt), expected);
+        let opening = Target {
+            position: target.position,
+            velocity: -target.velocity,
+        };
+        assert_eq!(law.command(&opening, &velocity, dt), na::zero());
+    }
+
+    #[test]
+    fn rate_limiter() {
+        let target = Target {
+            position: na::Vector3::new(0.0, 0.0, 1000.0),
+            velocity: na::Vector3::new(0.0, 0.0, -100.0),
+        };
+        let mut law = RateLimiter::new(Counting(0), 20.0);
+        let mut previous = na::zero();
+        let mut changes = 0;
+        for _ in 0..120 {
+            let command = law.command(&target, &na::zero(), 1.0 / 120.0);
+            if command != previous {
+                changes += 1;
+                previous = command;
+            }
+        }
+        assert_eq!(law.law.0, 20);
+        assert_eq!(changes, 20);
+    }
+
+    #[test]
+    fn acceleration_limit() {
+        let mut law = AccelerationLimit {
+            law: Counting(0),
+            max_accel: 2.5,
+        };
+        let target = Target {
+            position: na::Vector3::z(),
+            velocity: -na::Vector3::z(),
+        };
+        let commands = (0..4)
+            .map(|_| law.command(&target, &na::zero(), 0.1).x)
+            .collect::<Vec<_>>();
+        assert_eq!(commands, [1.0, 2.0, 2.5, 2.5]);
+    }
+
+    #[test]
+    fn latency() {
+        let target = Target {
+            position: na::Vector3::z(),
+            velocity: -na::Vector3::z(),
+        };
+        let dt = 0.01;
+        let mut law = Latency::new(Counting(0), 0.05);
+        for i in 0..20 {
+            let command = law.command(&target, &na::zero(), dt);
+            // The command computed on call `n` is released five calls later
+            let expected = if i < 5 { 0.0 } else { (i - 4) as f64 };
+            assert_eq!(command.x, expected);
+        }
+        let mut immediate = Latency::new(Counting(0), 0.0);
+        assert_eq!(immediate.command(&target, &na::zero(), dt).x, 1.0);
+    }
+
+    #[test]
+    fn tracked() {
+        let dt = 0.01;
+        let mut target = Target {
+            position: na::Vector3::new(100.0, 0.0, 5000.0),
+            velocity: na::Vector3::new(10.0, 0.0, -500.0),
+        };
+        let mut law = Tracked {
+            law: Ipn {
+                navigation_constant: 3.0,
+            },
+            filter: LosFilter::new(0.5, 0.2),
+        };
+        // With no velocity estimate yet, the target doesn't appear to be closing
+        assert_eq!(law.command(&target, &na::zero(), dt), na::zero());
+        let mut estimated = na::zero();
+        for _ in 0..200 {
+            target.position += target.velocity * dt;
+            estimated = law.command(&target, &na::zero(), dt);
+        }
+        let ideal = ipn(3.0, &target);
+        assert!((estimated - ideal).norm() < 1e-6 * ideal.norm());
+    }
+
+    #[test]
+    fn transient_suppression() {
+        let dt = 0.01;
+        let mut raw = Ipn {
+            navigation_constant: 3.0,
+        };
+        let mut smoothed = TransientSuppressor::new(raw, 50.0, 0.2);
+        let mut target = Target {
+            position: na::Vector3::new(100.0, 0.0, 5000.0),
+            velocity: na::Vector3::new(0.0, 0.0, -500.0),
+        };
+        for _ in 0..10 {
+            smoothed.command(&target, &na::zero(), dt);
+        }
+        let before = smoothed.command(&target, &na::zero(), dt);
+        // Reacquired track reports a substantial crossing velocity
+        target.velocity.x += 200.0;
+        let spike = raw.command(&target, &na::zero(), dt);
+        let mut previous = before;
+        for i in 1..=20 {
+            let command = smoothed.command(&target, &na::zero(), dt);
+            let step = (command - previous).norm();
+            assert!(step <= (spike - before).norm() * dt / 0.2 + 1e-9);
+            if i >= 20 {
+                assert!((command - spike).norm() < 1e-9);
+            }
+            previous = command;
+        }
+        assert!((spike - before).norm() > 10.0);
+    }
+}
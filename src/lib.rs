@@ -1,17 +1,78 @@
 //! Missile guidance helpers
 //!
+//! The core guidance and aiming math is `no_std` compatible. Disable the default `std` feature to
+//! build without an allocator; this omits helpers that allocate, such as `simulate`, `Latency`, and
+//! `PipelineConfig`.
+//!
+//! The `serde` feature derives `Serialize` and `Deserialize` for `Target`, `ManeuveringTarget`,
+//! and `PipelineConfig`, using nalgebra's serde integration for vectors.
+//!
 //! # References
 //! https://nptel.ac.in/courses/101108056/9
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
 use na::RealField;
 
+mod aim;
+mod airframe;
+mod estimation;
+mod evasion;
+mod filter;
+mod frame;
+mod fuze;
+mod law;
+mod motion;
+#[cfg(feature = "std")]
+mod pipeline;
+mod planar;
+mod profile;
+mod seeker;
+mod sim;
+mod threat;
+
+pub use aim::*;
+pub use airframe::*;
+pub use estimation::*;
+pub use evasion::*;
+pub use filter::*;
+pub use frame::*;
+pub use fuze::*;
+pub use law::*;
+pub use motion::*;
+#[cfg(feature = "std")]
+pub use pipeline::*;
+pub use planar::*;
+pub use profile::*;
+pub use seeker::*;
+pub use sim::*;
+pub use threat::*;
+
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Target<N: RealField> {
     pub position: na::Vector3<N>,
     pub velocity: na::Vector3<N>,
 }
 
 impl<N: RealField> Target<N> {
+    /// The target's state relative to a missile, from both of their inertial states
+    ///
+    /// This is the form guidance functions expect: the missile sits at the origin, and the
+    /// target's position and velocity are offset by the missile's.
+    #[inline]
+    pub fn relative(
+        missile_position: &na::Point3<N>,
+        missile_velocity: &na::Vector3<N>,
+        target_position: &na::Point3<N>,
+        target_velocity: &na::Vector3<N>,
+    ) -> Self {
+        Self {
+            position: target_position - missile_position,
+            velocity: target_velocity - missile_velocity,
+        }
+    }
+
     /// Whether the target is currently approaching the origin
     ///
     /// Shorthand for `position . velocity < 0`.
@@ -19,6 +80,291 @@ impl<N: RealField> Target<N> {
     pub fn is_closing(&self) -> bool {
         self.position.dot(&self.velocity) < na::zero()
     }
+
+    /// State after `dt` of constant-velocity motion, e.g. to dead-reckon through a loss of track
+    ///
+    /// Accelerations of both target and observer are neglected.
+    #[inline]
+    pub fn predict(&self, dt: N) -> Self {
+        Self {
+            position: self.position + self.velocity * dt,
+            velocity: self.velocity,
+        }
+    }
+
+    /// Relative angular momentum per unit mass about the origin, `position × velocity`
+    ///
+    /// Normal to the engagement plane. For an unaccelerated target, its magnitude divided by the
+    /// speed is the miss distance.
+    #[inline]
+    pub fn angular_momentum(&self) -> na::Vector3<N> {
+        self.position.cross(&self.velocity)
+    }
+
+    /// Sample a random target which is approaching, for property tests
+    ///
+    /// Positions lie within 10km and velocities within 1km/s on each axis.
+    #[cfg(any(test, feature = "rand"))]
+    pub fn arbitrary_closing<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        use rand::distributions::Uniform;
+        loop {
+            let position = na::Vector3::<f64>::from_distribution(&Uniform::new(-1e4, 1e4), rng);
+            let velocity = na::Vector3::<f64>::from_distribution(&Uniform::new(-1e3, 1e3), rng);
+            let dot = position.dot(&velocity);
+            if dot != 0.0 {
+                return Self {
+                    position: na::convert(position),
+                    velocity: na::convert(velocity * -dot.signum()),
+                };
+            }
+        }
+    }
+
+    /// Construct from a position and a direction of travel with an estimated speed
+    ///
+    /// Suitable for sensors which report a target's heading accurately but its speed only
+    /// coarsely. See `linear_aim` for how speed errors propagate into the solution.
+    #[inline]
+    pub fn from_position_and_heading(
+        position: na::Vector3<N>,
+        speed_estimate: N,
+        heading: na::Unit<na::Vector3<N>>,
+    ) -> Self {
+        Self {
+            position,
+            velocity: heading.into_inner() * speed_estimate,
+        }
+    }
+
+    /// Construct from plain `[x, y, z]` arrays, e.g. from another math library's vectors
+    #[inline]
+    pub fn from_arrays(position: [N; 3], velocity: [N; 3]) -> Self {
+        Self {
+            position: position.into(),
+            velocity: velocity.into(),
+        }
+    }
+
+    /// Decompose into plain `[x, y, z]` arrays of position and velocity
+    #[inline]
+    pub fn to_arrays(&self) -> ([N; 3], [N; 3]) {
+        (self.position.into(), self.velocity.into())
+    }
+
+    /// Convert to different units, where one old length unit is `length_scale` new ones and one
+    /// old time unit is `time_scale` new ones
+    ///
+    /// Geometry is preserved, so e.g. `is_closing` is unaffected.
+    #[inline]
+    pub fn scaled(&self, length_scale: N, time_scale: N) -> Self {
+        Self {
+            position: self.position * length_scale,
+            velocity: self.velocity * (length_scale / time_scale),
+        }
+    }
+}
+
+/// A target with known acceleration, relative to the pursuer like `Target`
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManeuveringTarget<N: RealField> {
+    pub position: na::Vector3<N>,
+    pub velocity: na::Vector3<N>,
+    /// Acceleration of the target, excluding the pursuer's own
+    pub acceleration: na::Vector3<N>,
+}
+
+impl<N: RealField> ManeuveringTarget<N> {
+    /// The instantaneous constant-velocity approximation of this target
+    #[inline]
+    pub fn tangent(&self) -> Target<N> {
+        Target {
+            position: self.position,
+            velocity: self.velocity,
+        }
+    }
+}
+
+/// Time from now until `target` switches between closing and opening, assuming constant velocity
+///
+/// Under unaccelerated motion this happens only once, at closest approach, so `None` is returned
+/// unless the target is currently closing.
+pub fn closing_transition_time<N: RealField>(target: &Target<N>) -> Option<N> {
+    let speed_squared = target.velocity.norm_squared();
+    if !target.is_closing() || speed_squared <= N::default_epsilon() {
+        return None;
+    }
+    Some(-target.position.dot(&target.velocity) / speed_squared)
+}
+
+/// Estimated time remaining until intercept, `-position·velocity / |velocity|²`
+///
+/// This is the time of closest approach for an unaccelerated target, and is only meaningful while
+/// `target.is_closing()`; zero is returned otherwise. See `time_to_go_at_speed` for launch
+/// planning, and `time_to_go_quadratic` to account for acceleration.
+pub fn time_to_go<N: RealField>(target: &Target<N>) -> N {
+    closing_transition_time(target).unwrap_or_else(na::zero)
+}
+
+/// Relative position and time of closest approach under constant velocity
+///
+/// The time is `time_to_go`, so a target that isn't closing is closest now.
+pub fn closest_approach<N: RealField>(target: &Target<N>) -> (na::Vector3<N>, N) {
+    let t = time_to_go(target);
+    (target.predict(t).position, t)
+}
+
+/// Time until a projectile launched now at `speed` intercepts `target`, from `linear_aim`
+///
+/// More accurate than `time_to_go` before launch, when the projectile's velocity isn't yet part of
+/// the relative velocity.
+pub fn time_to_go_at_speed<N: RealField>(target: &Target<N>, speed: N) -> Option<N> {
+    linear_aim(target, speed).map(|solution| solution.time)
+}
+
+/// Time until `target` reaches zero range, accounting for constant `relative_accel`
+///
+/// Models range as `r + r' t + r'' t^2 / 2`, with `r''` the relative acceleration along the line
+/// of sight. If the range never reaches zero, the time of minimum range is returned instead.
+/// Returns `None` if neither lies in the future, and zero if the range already is.
+pub fn time_to_go_quadratic<N: RealField>(
+    target: &Target<N>,
+    relative_accel: &na::Vector3<N>,
+) -> Option<N> {
+    let range = target.position.norm();
+    if range <= N::default_epsilon() {
+        return Some(na::zero());
+    }
+    let los = target.position / range;
+    let range_rate = los.dot(&target.velocity);
+    let half_accel = los.dot(relative_accel) * na::convert(0.5);
+    if half_accel.abs() <= N::default_epsilon() {
+        return if range_rate < na::zero() {
+            Some(-range / range_rate)
+        } else {
+            None
+        };
+    }
+    let discriminant = range_rate * range_rate - na::convert::<_, N>(4.) * half_accel * range;
+    let t = match discriminant.try_sqrt() {
+        Some(rt) => {
+            let t0 = (-range_rate - rt) / (na::convert::<_, N>(2.) * half_accel);
+            let t1 = (-range_rate + rt) / (na::convert::<_, N>(2.) * half_accel);
+            if t0 >= na::zero() && (t1 < na::zero() || t0 < t1) {
+                t0
+            } else {
+                t1
+            }
+        }
+        None => -range_rate / (na::convert::<_, N>(2.) * half_accel),
+    };
+    if t >= na::zero() {
+        Some(t)
+    } else {
+        None
+    }
+}
+
+/// Scale `accel` down to `max_magnitude` if it's any longer
+#[inline]
+pub fn clamp_acceleration<N: RealField>(accel: na::Vector3<N>, max_magnitude: N) -> na::Vector3<N> {
+    let magnitude = accel.norm();
+    if magnitude > max_magnitude {
+        accel * (max_magnitude / magnitude)
+    } else {
+        accel
+    }
+}
+
+/// Zero-effort miss: where `target` will be relative to us after `time_to_go` if neither
+/// accelerates
+#[inline]
+pub fn zem<N: RealField>(target: &Target<N>, time_to_go: N) -> na::Vector3<N> {
+    target.position + target.velocity * time_to_go
+}
+
+/// Proportional navigation in ZEM form, `N' * zem_perp / t_go^2`
+///
+/// `zem_perp` is the zero-effort miss perpendicular to the line of sight. Equivalent to PN for a
+/// consistent `time_to_go`, e.g. from `time_to_go`, but avoids dividing by the squared range,
+/// which vanishes near intercept. Returns zero if `time_to_go` is not positive.
+pub fn zem_guidance<N: RealField>(
+    navigation_constant: N,
+    target: &Target<N>,
+    time_to_go: N,
+) -> na::Vector3<N> {
+    if time_to_go <= N::default_epsilon() {
+        return na::zero();
+    }
+    let miss = zem(target, time_to_go);
+    let los = target.position.normalize();
+    (miss - los * los.dot(&miss)) * (navigation_constant / (time_to_go * time_to_go))
+}
+
+/// Optimal guidance for a missile under constant `gravity`, sometimes called explicit guidance
+///
+/// `zem_guidance` with the optimal navigation constant of 3, plus `-gravity` so that gravity drop
+/// is cancelled rather than left for the feedback to chase. `gravity` is the acceleration acting
+/// on the missile but not the target, e.g. against a powered target holding altitude. Matches
+/// `zem_guidance` when `gravity` is zero.
+pub fn optimal_guidance<N: RealField>(
+    target: &Target<N>,
+    time_to_go: N,
+    gravity: na::Vector3<N>,
+) -> na::Vector3<N> {
+    zem_guidance(na::convert(3.0), target, time_to_go) - gravity
+}
+
+/// Constant acceleration that removes zero-effort miss `zem` in exactly `time_to_go`
+///
+/// This is `2 * zem / t_go^2`, the kinematic core of ZEM-based guidance. Returns zero if
+/// `time_to_go` is not positive.
+pub fn accel_to_null_zem<N: RealField>(zem: &na::Vector3<N>, time_to_go: N) -> na::Vector3<N> {
+    if time_to_go <= N::default_epsilon() {
+        return na::zero();
+    }
+    zem * (na::convert::<_, N>(2.) / (time_to_go * time_to_go))
+}
+
+/// Optimal guidance against a target weaving sinusoidally at `weave_frequency` radians per unit
+/// time
+///
+/// `target_accel` and `target_jerk` are the target's current acceleration and its rate of change,
+/// from which the remainder of the weave is predicted exactly. The zero-effort miss accounting for
+/// that weave is nulled perpendicular to the line of sight with gain `navigation_constant`, for
+/// which 3 is optimal. As `weave_frequency` approaches zero, the target's acceleration and jerk
+/// are instead extrapolated as constants, so a target that isn't weaving is handled too.
+pub fn weave_optimal_guidance<N: RealField>(
+    target: &Target<N>,
+    target_accel: &na::Vector3<N>,
+    target_jerk: &na::Vector3<N>,
+    weave_frequency: N,
+    time_to_go: N,
+    navigation_constant: N,
+) -> na::Vector3<N> {
+    if time_to_go <= N::default_epsilon() {
+        return na::zero();
+    }
+    let w = weave_frequency;
+    let wt = w * time_to_go;
+    // Displacement per unit of current acceleration and of current jerk
+    let (from_accel, from_jerk) = if wt.abs() <= N::default_epsilon().sqrt() {
+        // Zero-frequency limits, which differ from the weave's terms by O(wt²) relative
+        let t = time_to_go;
+        (
+            t * t * na::convert(0.5),
+            t * t * t / na::convert(6.0),
+        )
+    } else {
+        ((N::one() - wt.cos()) / (w * w), (wt - wt.sin()) / (w * w * w))
+    };
+    let zem = target.position
+        + target.velocity * time_to_go
+        + target_accel * from_accel
+        + target_jerk * from_jerk;
+    let los = target.position.normalize();
+    let zem_perp = zem - los * los.dot(&zem);
+    zem_perp * (navigation_constant / (time_to_go * time_to_go))
 }
 
 /// Ideal Proportional Navigation
@@ -28,33 +374,638 @@ impl<N: RealField> Target<N> {
 /// `target.is_closing()` must be true.
 pub fn ipn<N: RealField>(navigation_constant: N, target: &Target<N>) -> na::Vector3<N> {
     debug_assert!(target.is_closing());
-    let w_s = target.position.cross(&target.velocity) / target.position.norm_squared();
-    (target.velocity * navigation_constant).cross(&w_s)
+    try_ipn(navigation_constant, target).unwrap_or_else(na::zero)
+}
+
+/// `ipn`, or `None` if the target isn't closing or is too near for the line of sight to be defined
+pub fn try_ipn<N: RealField>(navigation_constant: N, target: &Target<N>) -> Option<na::Vector3<N>> {
+    if !target.is_closing() || target.position.norm_squared() <= N::default_epsilon() {
+        return None;
+    }
+    let acceleration = (target.velocity * navigation_constant).cross(&los_rate(target));
+    debug_assert_perpendicular(&acceleration, &target.velocity);
+    Some(acceleration)
+}
+
+/// Proportional Navigation using the magnitude of the range rate, for targets which may be
+/// receding
+///
+/// Commands `N / r * (|V_c| * v_⊥ + |v_⊥|^2 * r̂)`, where `V_c` is the closing speed and `v_⊥` the
+/// relative velocity perpendicular to the line of sight `r̂`. While closing this is exactly `ipn`,
+/// i.e. `tpn` plus a retro term along the line of sight. Once the range opens, `ipn` would steer
+/// against the crossing motion; taking the magnitude keeps leading it instead. The retro term
+/// then decelerates the opening by `(N - 1) * |v_⊥|^2 / r`, outweighing the rate at which
+/// unaccelerated motion opens the range, which would otherwise strand `tpn` scaled by `|V_c|` as
+/// the range rate passes through zero.
+///
+/// Valid for `N > 1` wherever the range is nonzero and, if it's opening, there's some crossing
+/// motion to act on: a target receding exactly along the line of sight draws no command. Intercept
+/// is only possible if the pursuer can eventually outpace the target.
+pub fn retro_pn<N: RealField>(navigation_constant: N, target: &Target<N>) -> na::Vector3<N> {
+    let range = target.position.norm();
+    let los = target.position / range;
+    let closing = -target.velocity.dot(&los);
+    let perpendicular = target.velocity + los * closing;
+    (perpendicular * closing.abs() + los * perpendicular.norm_squared())
+        * (navigation_constant / range)
+}
+
+/// Check the defining property of `ipn`'s output: it never changes the relative speed
+///
+/// The tolerance scales with `N`'s precision, so `f32` rounding isn't mistaken for a violation.
+#[inline]
+fn debug_assert_perpendicular<N: RealField>(
+    acceleration: &na::Vector3<N>,
+    velocity: &na::Vector3<N>,
+) {
+    debug_assert!(
+        acceleration.dot(velocity).abs()
+            <= acceleration.norm()
+                * velocity.norm()
+                * (N::default_epsilon() * na::convert(64.0)),
+        "IPN acceleration must be perpendicular to the relative velocity"
+    );
+}
+
+/// Augmented Proportional Navigation
+///
+/// Adds `N / 2` times the target's acceleration perpendicular to the line of sight to `ipn`,
+/// compensating for target maneuvers that `ipn` would otherwise lag behind. Identical to `ipn` for
+/// an unaccelerated target.
+///
+/// `target.tangent().is_closing()` must be true.
+pub fn apn<N: RealField>(navigation_constant: N, target: &ManeuveringTarget<N>) -> na::Vector3<N> {
+    let los = target.position.normalize();
+    let lateral = target.acceleration - los * los.dot(&target.acceleration);
+    ipn(navigation_constant, &target.tangent()) + lateral * (navigation_constant * na::convert(0.5))
+}
+
+/// Biased Proportional Navigation, for impact-angle control
+///
+/// Adds `2 * |v| * d_⊥ / t_go` to the `ipn` command, where `d_⊥` is the component of
+/// `desired_impact_direction` perpendicular to the relative velocity `v`, turning the relative
+/// velocity towards that direction by the time of impact. The bias vanishes as `t_go` grows,
+/// leaving ordinary `ipn`. With a navigation constant of 6 this is the minimum-energy command
+/// meeting both the miss and impact direction constraints in the linearized engagement. Returns
+/// zero if `time_to_go` is not positive.
+///
+/// `target.is_closing()` must be true.
+pub fn bpn<N: RealField>(
+    navigation_constant: N,
+    target: &Target<N>,
+    desired_impact_direction: &na::Unit<na::Vector3<N>>,
+    time_to_go: N,
+) -> na::Vector3<N> {
+    if time_to_go <= N::default_epsilon() {
+        return na::zero();
+    }
+    let forward = target.velocity.normalize();
+    let d = desired_impact_direction.as_ref();
+    let error = d - forward * forward.dot(d);
+    let bias = error * (na::convert::<_, N>(2.0) * target.velocity.norm() / time_to_go);
+    ipn(navigation_constant, target) + bias
+}
+
+/// Model-predictive guidance: the first command of the best short sequence of candidate commands
+///
+/// Each candidate sequence holds one command for the first half of `horizon` and another for the
+/// second half, then coasts. Commands are either zero or `max_accel` in one of `candidates`
+/// directions evenly spaced about the line of sight. Sequences are scored by the closest approach
+/// predicted by propagating `target`'s known acceleration through the horizon, and the first
+/// command of the best one is returned, to be re-planned on the next step. Evaluates
+/// `(candidates + 1)²` sequences per call.
+pub fn mpc_guidance<N: RealField>(
+    target: &ManeuveringTarget<N>,
+    horizon: N,
+    candidates: usize,
+    max_accel: N,
+) -> na::Vector3<N> {
+    let los = match target.position.try_normalize(N::default_epsilon()) {
+        Some(los) => los,
+        None => return na::zero(),
+    };
+    let reference = if los.x.abs() < na::convert(0.9) {
+        na::Vector3::x()
+    } else {
+        na::Vector3::y()
+    };
+    let u = los.cross(&reference).normalize();
+    let w = los.cross(&u);
+    let command = |i: usize| {
+        if i == 0 {
+            return na::zero();
+        }
+        let angle = N::two_pi() * na::convert((i - 1) as f64) / na::convert(candidates as f64);
+        (u * angle.cos() + w * angle.sin()) * max_accel
+    };
+    let half = horizon * na::convert(0.5);
+    let mut best = (N::max_value(), na::zero());
+    for first in 0..=candidates {
+        for second in 0..=candidates {
+            let miss = predicted_miss_under(target, &[command(first), command(second)], half);
+            if miss < best.0 {
+                best = (miss, command(first));
+            }
+        }
+    }
+    best.1
+}
+
+/// Closest approach of `target` while the pursuer holds each of `commands` for `duration` in
+/// turn, then coasts
+fn predicted_miss_under<N: RealField>(
+    target: &ManeuveringTarget<N>,
+    commands: &[na::Vector3<N>],
+    duration: N,
+) -> N {
+    const SAMPLES: usize = 8;
+    let half = na::convert::<_, N>(0.5);
+    let (mut position, mut velocity) = (target.position, target.velocity);
+    let mut closest = position.norm();
+    for command in commands {
+        let accel = target.acceleration - command;
+        let at = |t: N| position + velocity * t + accel * (half * t * t);
+        for i in 1..=SAMPLES {
+            let t = duration * na::convert(i as f64) / na::convert(SAMPLES as f64);
+            closest = closest.min(at(t).norm());
+        }
+        position = at(duration);
+        velocity += accel * duration;
+    }
+    let t = (-position.dot(&velocity) / velocity.norm_squared()).max(na::zero());
+    if t.is_finite() {
+        closest = closest.min((position + velocity * t).norm());
+    }
+    closest
+}
+
+/// `ipn` for a vehicle that can only maneuver in the plane containing its velocity and
+/// perpendicular to `plane_normal`, e.g. one with pitch but no yaw authority
+///
+/// Returns the achievable in-plane command, and the out-of-plane demand which was discarded.
+/// Their sum is the `ipn` command. The component of `plane_normal` along `current_velocity` is
+/// ignored, so the plane always contains the velocity.
+///
+/// `target.is_closing()` must be true.
+pub fn planar_constrained_ipn<N: RealField>(
+    navigation_constant: N,
+    target: &Target<N>,
+    current_velocity: &na::Vector3<N>,
+    plane_normal: &na::Unit<na::Vector3<N>>,
+) -> (na::Vector3<N>, na::Vector3<N>) {
+    let command = ipn(navigation_constant, target);
+    let normal = match current_velocity.try_normalize(N::default_epsilon()) {
+        Some(forward) => {
+            let normal = plane_normal.as_ref() - forward * forward.dot(plane_normal);
+            normal.try_normalize(N::default_epsilon())
+        }
+        None => Some(plane_normal.into_inner()),
+    };
+    match normal {
+        Some(normal) => {
+            let out_of_plane = normal * normal.dot(&command);
+            (command - out_of_plane, out_of_plane)
+        }
+        // The normal lies along the velocity, so no lateral maneuver is constrained
+        None => (command, na::zero()),
+    }
+}
+
+/// Pure pursuit: steer the velocity directly toward the target's current position
+///
+/// Returns the lateral acceleration which, held constant relative to the velocity, curves along
+/// the circular arc tangent to `current_velocity` that passes through the target:
+/// `2 * |v|^2 * sin(e) / r`, with `e` the angle between velocity and line of sight. Against a
+/// crossing target this ends in a tail chase, so requires more turn authority than PN.
+pub fn pure_pursuit<N: RealField>(
+    target: &Target<N>,
+    current_velocity: &na::Vector3<N>,
+) -> na::Vector3<N> {
+    arc_toward(&target.position, current_velocity)
+}
+
+/// Lead pursuit: steer toward the predicted intercept point rather than the target itself
+///
+/// As `pure_pursuit`, but aiming for the point at which `linear_aim` at the current speed would
+/// intercept. Falls back to pure pursuit if there's no intercept.
+pub fn lead_pursuit<N: RealField>(
+    target: &Target<N>,
+    current_velocity: &na::Vector3<N>,
+) -> na::Vector3<N> {
+    let inertial = Target {
+        position: target.position,
+        velocity: target.velocity + current_velocity,
+    };
+    let point = match linear_aim(&inertial, current_velocity.norm()) {
+        Some(solution) => solution.point,
+        None => target.position,
+    };
+    arc_toward(&point, current_velocity)
+}
+
+/// Command to line of sight: steer a missile onto the beam from a tracker to the target
+///
+/// All vectors are relative to the tracker. Returns acceleration perpendicular to the beam of
+/// `-stiffness * d - damping * v`, where `d` and `v` are the missile's displacement from and
+/// velocity across the beam, so the offset decays like a damped spring. The beam's own rotation is
+/// neglected, which lags a crossing target slightly.
+pub fn clos_guidance<N: RealField>(
+    tracker_to_target: &na::Vector3<N>,
+    tracker_to_missile: &na::Vector3<N>,
+    missile_velocity: &na::Vector3<N>,
+    stiffness: N,
+    damping: N,
+) -> na::Vector3<N> {
+    let beam = tracker_to_target.normalize();
+    let perpendicular = |x: &na::Vector3<N>| x - beam * beam.dot(x);
+    -(perpendicular(tracker_to_missile) * stiffness + perpendicular(missile_velocity) * damping)
+}
+
+/// Lateral acceleration following the circular arc tangent to `velocity` through `point`
+fn arc_toward<N: RealField>(point: &na::Vector3<N>, velocity: &na::Vector3<N>) -> na::Vector3<N> {
+    let forward = match velocity.try_normalize(N::default_epsilon()) {
+        Some(forward) => forward,
+        None => return na::zero(),
+    };
+    let range_squared = point.norm_squared();
+    if range_squared <= N::default_epsilon() {
+        return na::zero();
+    }
+    // |lateral| / r = sin(e), so 2 v^2 sin(e) / r = 2 v^2 |lateral| / r^2
+    let lateral = point - forward * forward.dot(point);
+    lateral * (na::convert::<_, N>(2.) * velocity.norm_squared() / range_squared)
+}
+
+/// `ipn` with repulsion from `obstacles`, such as friendly units, within `avoidance_radius`
+///
+/// Each obstacle within the radius adds up to `avoidance_gain` acceleration, falling off smoothly
+/// as `(1 - d / avoidance_radius)²` with distance `d`. Repulsion is directed away from the
+/// obstacle perpendicular to its relative velocity, so that it steers around obstacles on the
+/// direct path rather than merely braking.
+///
+/// `target.is_closing()` must be true.
+pub fn avoidance_ipn<N: RealField>(
+    navigation_constant: N,
+    target: &Target<N>,
+    obstacles: &[Target<N>],
+    avoidance_radius: N,
+    avoidance_gain: N,
+) -> na::Vector3<N> {
+    let mut acceleration = ipn(navigation_constant, target);
+    for obstacle in obstacles {
+        let distance = obstacle.position.norm();
+        if distance >= avoidance_radius {
+            continue;
+        }
+        let away = match obstacle.velocity.try_normalize(N::default_epsilon()) {
+            Some(v) => v * v.dot(&obstacle.position) - obstacle.position,
+            None => -obstacle.position,
+        };
+        let direction = match away.try_normalize(N::default_epsilon()) {
+            Some(direction) => direction,
+            // Dead ahead; any perpendicular will do
+            None => {
+                let axis = obstacle.velocity.cross(&na::Vector3::x());
+                axis.try_normalize(N::default_epsilon()).unwrap_or_else(na::Vector3::y)
+            }
+        };
+        let falloff = N::one() - distance / avoidance_radius;
+        acceleration += direction * (avoidance_gain * falloff * falloff);
+    }
+    acceleration
+}
+
+/// Miss distance of a pursuer launched at `speed` along the line of sight and flying `ipn`,
+/// without simulation
+///
+/// `target` is relative to the launcher, which is at rest. The launch adds `speed` towards the
+/// target to the closing velocity, leaving a heading error between the relative velocity and the
+/// line of sight that `ipn` then removes; see `predicted_miss`. Too slow a launch against a
+/// receding target never closes, and misses by the current range.
+pub fn predicted_miss_pn<N: RealField>(navigation_constant: N, target: &Target<N>, speed: N) -> N {
+    let los = match target.position.try_normalize(N::default_epsilon()) {
+        Some(los) => los,
+        None => return na::zero(),
+    };
+    let launched = Target {
+        position: target.position,
+        velocity: target.velocity - los * speed,
+    };
+    predicted_miss(&launched, navigation_constant)
+}
+
+/// Miss distance `ipn` will achieve against `target` in continuous time, without simulation
+///
+/// Under ideal proportional navigation the relative speed is constant and the heading error `e`
+/// between relative velocity and line of sight obeys `sin(e) * r^(1 - N) = const`. For `N >= 1`
+/// and a closing target the heading error decays to zero and intercept is exact; for smaller
+/// constants the heading error grows to 90 degrees at a closest approach of
+/// `r * sin(e)^(1 / (1 - N))`. The relative speed doesn't appear, so none is needed.
+pub fn predicted_miss<N: RealField>(target: &Target<N>, navigation_constant: N) -> N {
+    let range = target.position.norm();
+    if !target.is_closing() {
+        return range;
+    }
+    if navigation_constant >= N::one() {
+        return na::zero();
+    }
+    let sin_error = target.angular_momentum().norm() / (range * target.velocity.norm());
+    range * sin_error.powf(N::one() / (N::one() - navigation_constant))
+}
+
+/// True Proportional Navigation
+///
+/// Commands `N * V_c * w_s` perpendicular to the line of sight, where `V_c` is the closing speed,
+/// rather than perpendicular to the relative velocity as `ipn` does. The two agree for targets
+/// approaching nearly head-on, and diverge for fast crossing targets. Because the command has no
+/// component along the line of sight, it cannot raise a low closing speed as `ipn` can.
+///
+/// `target.is_closing()` must be true.
+pub fn tpn<N: RealField>(navigation_constant: N, target: &Target<N>) -> na::Vector3<N> {
+    debug_assert!(target.is_closing());
+    let los = target.position.normalize();
+    los_rate(target).cross(&los) * (navigation_constant * closing_speed(target))
+}
+
+/// Relative-velocity True Proportional Navigation
+///
+/// Commands `N * V_c * w_s` like `tpn`, but perpendicular to the relative velocity like `ipn`.
+/// Equivalent to `ipn` scaled by the ratio of closing speed to relative speed, so it demands less
+/// acceleration than `ipn` against crossing targets.
+///
+/// `target.is_closing()` must be true.
+pub fn rtpn<N: RealField>(navigation_constant: N, target: &Target<N>) -> na::Vector3<N> {
+    debug_assert!(target.is_closing());
+    let direction = target.velocity.normalize();
+    direction.cross(&los_rate(target)) * (navigation_constant * closing_speed(target))
+}
+
+/// Parallel navigation: hold the line of sight's inertial direction fixed
+///
+/// Commands `gain` times the relative velocity perpendicular to the line of sight, so that
+/// component, and with it the LOS rate, decays exponentially at rate `gain`. A target whose LOS
+/// doesn't rotate is on a collision course. PN approximates this with a gain that rises as
+/// intercept approaches: `tpn` is parallel navigation with `gain = N * V_c / r`, i.e. `N / t_go`.
+pub fn parallel_navigation<N: RealField>(target: &Target<N>, gain: N) -> na::Vector3<N> {
+    let los = target.position.normalize();
+    (target.velocity - los * los.dot(&target.velocity)) * gain
+}
+
+/// Angular velocity of the line of sight, `position × velocity / |position|²`
+///
+/// Zero for a target on a collision course. Undefined at zero range.
+pub fn los_rate<N: RealField>(target: &Target<N>) -> na::Vector3<N> {
+    target.angular_momentum() / target.position.norm_squared()
+}
+
+/// Rate at which range is decreasing, `-position · velocity / |position|`
+///
+/// Positive exactly when `target.is_closing()`.
+pub fn closing_speed<N: RealField>(target: &Target<N>) -> N {
+    -target.position.dot(&target.velocity) / target.position.norm()
+}
+
+/// Whether the target is closing with a line of sight rotating slower than `tolerance`
+///
+/// The mariner's test of constant bearing and decreasing range: without acceleration on either
+/// side, such a target is on course for a collision.
+pub fn is_collision_course<N: RealField>(target: &Target<N>, tolerance: N) -> bool {
+    target.is_closing() && los_rate(target).norm() < tolerance
+}
+
+/// Navigation constant to which an arbitrary guidance `command` corresponds
+///
+/// Inverts the proportional navigation relation `|a| = N' * V * |w_s|`, where `V` is the relative
+/// speed and `w_s` the line-of-sight rate, allowing different laws to be compared on equal
+/// footing. Only the component of `command` perpendicular to `current_velocity` is considered,
+/// since along-track acceleration does not steer. Returns zero if the line of sight is not
+/// rotating.
+pub fn effective_n<N: RealField>(
+    command: &na::Vector3<N>,
+    target: &Target<N>,
+    current_velocity: &na::Vector3<N>,
+) -> N {
+    let lateral = match current_velocity.try_normalize(N::default_epsilon()) {
+        Some(forward) => command - forward * forward.dot(command),
+        None => *command,
+    };
+    let demand = target.velocity.norm() * los_rate(target).norm();
+    if demand <= N::default_epsilon() {
+        return na::zero();
+    }
+    lateral.norm() / demand
+}
+
+/// Numerical thresholds for the straight-line aim solvers, e.g. `linear_aim_with`
+///
+/// The defaults scale with `N::default_epsilon()`, so suit both `f32` and `f64`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Tolerances<N: RealField> {
+    /// Difference between the squared speeds of target and projectile, relative to their sum,
+    /// below which they're treated as equal
+    pub degeneracy: N,
+    /// Range below which the target is treated as coincident with the launch point, leaving no
+    /// direction to aim in
+    pub zero_range: N,
+    /// Magnitude of a negative discriminant, relative to the terms it's computed from, below which
+    /// it's clamped to zero so that grazing intercepts survive rounding
+    pub discriminant: N,
+}
+
+impl<N: RealField> Default for Tolerances<N> {
+    fn default() -> Self {
+        Self {
+            degeneracy: N::default_epsilon().sqrt(),
+            zero_range: N::default_epsilon(),
+            discriminant: N::default_epsilon() * na::convert(4.0),
+        }
+    }
+}
+
+/// A solution to an aiming problem
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AimSolution<N: RealField> {
+    /// Direction in which to launch the projectile
+    pub direction: na::Unit<na::Vector3<N>>,
+    /// Time from launch until impact
+    pub time: N,
+    /// Point of impact, relative to the launch point
+    pub point: na::Vector3<N>,
+    /// Launch speed of the projectile
+    pub required_speed: N,
+}
+
+impl<N: RealField> From<AimSolution<N>> for (na::Unit<na::Vector3<N>>, N) {
+    /// The `(direction, time)` pair formerly returned by the aim family
+    fn from(solution: AimSolution<N>) -> Self {
+        (solution.direction, solution.time)
+    }
+}
+
+/// Kinetic energy of impact between a projectile of `projectile_mass` and `target`
+///
+/// Computes `0.5 * m * v^2` from the relative speed, which for unaccelerated motion is also the
+/// closing speed at the moment of impact.
+pub fn impact_energy<N: RealField>(target: &Target<N>, projectile_mass: N) -> N {
+    na::convert::<_, N>(0.5) * projectile_mass * target.velocity.norm_squared()
 }
 
 /// Direction to aim a projectile that will travel at `speed` to hit `target` and time of impact
-pub fn linear_aim<N: RealField>(
+///
+/// An error `dv` in the target's velocity moves the predicted intercept point by roughly
+/// `dv * t`, growing without bound as `speed` approaches the target's speed. Speed estimates from
+/// `Target::from_position_and_heading` should be judged against that.
+pub fn linear_aim<N: RealField>(target: &Target<N>, speed: N) -> Option<AimSolution<N>> {
+    linear_aim_with(target, speed, &Tolerances::default())
+}
+
+/// `linear_aim` with explicit numerical `tolerances`
+pub fn linear_aim_with<N: RealField>(
     target: &Target<N>,
     speed: N,
-) -> Option<(na::Unit<na::Vector3<N>>, N)> {
+    tolerances: &Tolerances<N>,
+) -> Option<AimSolution<N>> {
+    let times = intercept_times(
+        target.position.norm_squared(),
+        target.position.dot(&target.velocity),
+        target.velocity.norm_squared(),
+        speed,
+        tolerances,
+    );
+    // Only the first solution is built, keeping the common case cheap
+    match times {
+        Some([early, _]) => early.map(|t| linear_solution(target, speed, t)),
+        None => Some(degenerate_solution(target, speed)),
+    }
+}
+
+/// `linear_aim`, treating speeds as matched when their squares differ by less than `eps` relative
+/// to their sum
+///
+/// Shorthand for `linear_aim_with` with `Tolerances::degeneracy` set to `eps`. Being relative, the
+/// threshold doesn't depend on the problem's coordinate scale.
+pub fn linear_aim_eps<N: RealField>(
+    target: &Target<N>,
+    speed: N,
+    eps: N,
+) -> Option<AimSolution<N>> {
+    let tolerances = Tolerances {
+        degeneracy: eps,
+        ..Tolerances::default()
+    };
+    linear_aim_with(target, speed, &tolerances)
+}
+
+/// Every straight-line intercept of `target` by a projectile travelling at `speed`, earliest first
+///
+/// A target faster than the projectile may be intercepted twice: once on its way in, and again
+/// later on its way out, trading a longer flight for a different approach geometry. Slots without
+/// a solution are `None`, and always follow those with one. `linear_aim` returns the first.
+pub fn linear_aim_solutions<N: RealField>(
+    target: &Target<N>,
+    speed: N,
+) -> [Option<AimSolution<N>>; 2] {
+    linear_aim_solutions_with(target, speed, &Tolerances::default())
+}
+
+/// `linear_aim_solutions` with explicit numerical `tolerances`
+pub fn linear_aim_solutions_with<N: RealField>(
+    target: &Target<N>,
+    speed: N,
+    tolerances: &Tolerances<N>,
+) -> [Option<AimSolution<N>>; 2] {
+    let times = intercept_times(
+        target.position.norm_squared(),
+        target.position.dot(&target.velocity),
+        target.velocity.norm_squared(),
+        speed,
+        tolerances,
+    );
+    let solution = |t| linear_solution(target, speed, t);
+    match times {
+        Some([early, late]) => [early.map(solution), late.map(solution)],
+        None => [Some(degenerate_solution(target, speed)), None],
+    }
+}
+
+/// Aim at where `target` will be after `time`
+#[inline]
+fn linear_solution<N: RealField>(target: &Target<N>, speed: N, time: N) -> AimSolution<N> {
+    let point = target.position + target.velocity * time;
+    AimSolution {
+        direction: na::Unit::new_normalize(point),
+        time,
+        point,
+        required_speed: speed,
+    }
+}
+
+/// Aim directly at `target` when the speeds are matched
+#[inline]
+fn degenerate_solution<N: RealField>(target: &Target<N>, speed: N) -> AimSolution<N> {
+    AimSolution {
+        direction: na::Unit::new_normalize(target.position),
+        time: na::zero(),
+        point: target.position,
+        required_speed: speed,
+    }
+}
+
+/// Non-negative times at which a projectile travelling at `speed` meets a target, earliest first
+///
+/// The target is described by `p·p`, `p·v` and `v·v`, independent of dimension. `None` if the
+/// speeds are matched within `tolerances`, leaving the problem degenerate. There are no solutions
+/// within `tolerances.zero_range` of the origin, or if any input is NaN or infinite.
+pub(crate) fn intercept_times<N: RealField>(
+    range_squared: N,
+    position_dot_velocity: N,
+    target_speed_squared: N,
+    speed: N,
+    tolerances: &Tolerances<N>,
+) -> Option<[Option<N>; 2]> {
+    let finite = (range_squared + position_dot_velocity + target_speed_squared + speed).is_finite();
+    if !finite || range_squared <= tolerances.zero_range * tolerances.zero_range {
+        return Some([None, None]);
+    }
     // t^2 * -s^2 + t * (2.0 * v_T·p_T) + p_T·p_T = 0
-    let a = target.velocity.norm_squared() - (speed * speed);
-    if a.abs() < na::convert(1e-3) {
-        return Some((na::Unit::new_normalize(target.position), na::zero()));
+    let speed_squared = speed * speed;
+    let a = target_speed_squared - speed_squared;
+    if a.abs() <= tolerances.degeneracy * (target_speed_squared + speed_squared) {
+        return None;
+    }
+    let b: N = na::convert::<_, N>(2.) * position_dot_velocity;
+    let c = range_squared;
+    let four_ac = na::convert::<_, N>(4.) * a * c;
+    let mut discriminant = b * b - four_ac;
+    let rounding = tolerances.discriminant * (b * b + four_ac.abs());
+    if discriminant < na::zero() && -discriminant <= rounding {
+        discriminant = na::zero();
     }
-    let b: N = na::convert::<_, N>(2.) * target.position.dot(&target.velocity);
-    let c = target.position.norm_squared();
-    let rt = (b * b - na::convert::<_, N>(4.) * a * c).try_sqrt()?;
+    let rt = match discriminant.try_sqrt() {
+        Some(rt) => rt,
+        None => return Some([None, None]),
+    };
     let t0 = (-b + rt) / (na::convert::<_, N>(2.) * a);
     let t1 = (-b - rt) / (na::convert::<_, N>(2.) * a);
-    let t = [t0, t1]
-        .iter()
-        .cloned()
-        .filter(|&x| x >= na::zero())
-        .min_by(|x, y| x.partial_cmp(y).unwrap())
-        .unwrap();
-    let pos = target.position + target.velocity * t;
-    Some((na::Unit::new_normalize(pos), t))
+    // Preferring t0 on ties
+    let (early, late) = if t1 < t0 { (t1, t0) } else { (t0, t1) };
+    // Comparisons are false for NaN, so a NaN root is never returned
+    Some(if early >= na::zero() {
+        [Some(early), Some(late).filter(|&t| t >= na::zero())]
+    } else if late >= na::zero() {
+        [Some(late), None]
+    } else {
+        [None, None]
+    })
+}
+
+/// `linear_aim` returning a `(direction, time)` tuple, as it once did
+#[deprecated(note = "use `linear_aim`, which returns an `AimSolution`")]
+pub fn linear_aim_tuple<N: RealField>(
+    target: &Target<N>,
+    speed: N,
+) -> Option<(na::Unit<na::Vector3<N>>, N)> {
+    linear_aim(target, speed).map(Into::into)
 }
 
 /// Change in velocity to steer an in-flight projectile towards `target`
@@ -64,9 +1015,49 @@ pub fn linear_steer<N: RealField>(target: &Target<N>, current_velocity: &na::Vec
         velocity: target.velocity + current_velocity,
     };
     let current_speed = current_velocity.norm();
-    let (dir, t) = linear_aim(&target, average_speed)?;
-    let goal = dir.into_inner() * current_speed;
-    Some((goal - current_velocity, t))
+    let solution = linear_aim(&target, average_speed)?;
+    let goal = solution.direction.into_inner() * current_speed;
+    Some((goal - current_velocity, solution.time))
+}
+
+/// Magnitude of the change in velocity `linear_steer` calls for
+///
+/// Lets planners reject intercepts beyond a projectile's remaining maneuver budget.
+pub fn required_delta_v<N: RealField>(
+    target: &Target<N>,
+    current_velocity: &na::Vector3<N>,
+    average_speed: N,
+) -> Option<N> {
+    linear_steer(target, current_velocity, average_speed).map(|(delta_v, _)| delta_v.norm())
+}
+
+/// `linear_steer`, also reporting whether the steered velocity lies within `max_look_angle` of
+/// `current_velocity`
+///
+/// Models a seeker looking along the velocity vector with a limited gimbal: when the required lead
+/// exceeds the limit, the flag is false and the target would be lost from view.
+pub fn linear_steer_fov<N: RealField>(
+    target: &Target<N>,
+    current_velocity: &na::Vector3<N>,
+    average_speed: N,
+    max_look_angle: N,
+) -> Option<(na::Vector3<N>, N, bool)> {
+    let (delta_v, time) = linear_steer(target, current_velocity, average_speed)?;
+    let look_angle = (current_velocity + delta_v).angle(current_velocity);
+    Some((delta_v, time, look_angle <= max_look_angle))
+}
+
+/// Confirms the core API is usable without `std`, by building with `--no-default-features`
+#[cfg(not(feature = "std"))]
+#[allow(dead_code)]
+mod no_std_check {
+    use super::*;
+
+    fn core_api(target: &Target<f32>, current_velocity: &na::Vector3<f32>) {
+        let _ = ipn(3.0, target);
+        let _ = linear_aim(target, 500.0);
+        let _ = linear_steer(target, current_velocity, 500.0);
+    }
 }
 
 #[cfg(test)]
@@ -103,24 +1094,1141 @@ mod tests {
         );
     }
 
+    #[test]
+    fn arrays_round_trip() {
+        let target = Target::from_arrays([1.0f32, -2.0, 3.5], [0.25, 0.0, -4.0]);
+        assert_eq!(target.position, na::Vector3::new(1.0, -2.0, 3.5));
+        assert_eq!(target.velocity, na::Vector3::new(0.25, 0.0, -4.0));
+        assert_eq!(target.to_arrays(), ([1.0, -2.0, 3.5], [0.25, 0.0, -4.0]));
+    }
+
+    #[test]
+    fn effective_n_inverts_ipn() {
+        // Stationary target, so the missile's own velocity is the negated relative velocity
+        let missile_velocity = na::Vector3::new(0.0f64, 0.5, 1.0);
+        let target = Target {
+            position: na::Vector3::new(3.0, -1.0, 10.0),
+            velocity: -missile_velocity,
+        };
+        let command = ipn(3.0, &target);
+        assert!((effective_n(&command, &target, &missile_velocity) - 3.0).abs() < 1e-6);
+        assert_eq!(
+            effective_n(
+                &command,
+                &Target {
+                    position: na::Vector3::new(0.0, 0.0, 10.0),
+                    velocity: na::Vector3::new(0.0, 0.0, -1.0),
+                },
+                &missile_velocity
+            ),
+            0.0
+        );
+    }
+
+    #[test]
+    fn heading_speed_error() {
+        let position = na::Vector3::new(0.0, 1000.0, 3000.0);
+        let heading = na::Unit::new_normalize(na::Vector3::new(1.0, 0.0, -0.5));
+        let true_speed = 200.0;
+        let intercept = |speed: f64| {
+            let target = Target::from_position_and_heading(position, speed, heading);
+            let solution = linear_aim(&target, 800.0).unwrap();
+            (solution.point, solution.time)
+        };
+        let (truth, t) = intercept(true_speed);
+        let (estimate, _) = intercept(0.9 * true_speed);
+        let shift = (truth - estimate).norm();
+        assert!(shift > 0.0);
+        // Within twice the naive `dv * t` bound at a 4:1 speed advantage
+        assert!(shift < 2.0 * 0.1 * true_speed * t);
+    }
+
+    #[test]
+    fn impact_energy_scales() {
+        let head_on = Target {
+            position: na::Vector3::new(0.0, 0.0, 5000.0),
+            velocity: na::Vector3::new(0.0, 0.0, -1500.0),
+        };
+        let glancing = Target {
+            position: na::Vector3::new(0.0, 300.0, 5000.0),
+            velocity: na::Vector3::new(0.0, -20.0, -100.0),
+        };
+        assert_eq!(impact_energy(&head_on, 2.0), 0.5 * 2.0 * 1500.0 * 1500.0);
+        assert!(impact_energy(&head_on, 2.0) > 100.0 * impact_energy(&glancing, 2.0));
+    }
+
+    #[test]
+    fn closing_transition() {
+        let passing = Target::<f64> {
+            position: na::Vector3::new(-100.0, 10.0, 0.0),
+            velocity: na::Vector3::new(20.0, 0.0, 0.0),
+        };
+        let t = closing_transition_time(&passing).unwrap();
+        assert!((t - 5.0).abs() < 1e-12);
+        let later = passing.position + passing.velocity * t;
+        assert!(later.dot(&passing.velocity).abs() < 1e-9);
+
+        let receding = Target {
+            position: na::Vector3::new(100.0, 10.0, 0.0),
+            velocity: na::Vector3::new(20.0, 0.0, 0.0),
+        };
+        assert_eq!(closing_transition_time(&receding), None);
+        let stationary = Target {
+            position: na::Vector3::new(100.0, 10.0, 0.0),
+            velocity: na::zero(),
+        };
+        assert_eq!(closing_transition_time(&stationary), None);
+    }
+
+    #[test]
+    fn closing_time_to_go() {
+        let head_on = Target::<f64> {
+            position: na::Vector3::new(0.0, 0.0, -1000.0),
+            velocity: na::Vector3::new(0.0, 0.0, 250.0),
+        };
+        let expected = head_on.position.norm() / closing_speed(&head_on);
+        assert!((time_to_go(&head_on) - expected).abs() < 1e-12);
+        let opening = Target {
+            velocity: -head_on.velocity,
+            ..head_on
+        };
+        assert_eq!(time_to_go(&opening), 0.0);
+
+        // A stationary target is reached after range / speed
+        let stationary = Target {
+            velocity: na::zero(),
+            ..head_on
+        };
+        let t = time_to_go_at_speed(&stationary, 500.0).unwrap();
+        assert!((t - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn quadratic_time_to_go() {
+        let target = Target::<f64> {
+            position: na::Vector3::new(0.0, 0.0, 1000.0),
+            velocity: na::Vector3::new(0.0, 0.0, -250.0),
+        };
+        let linear = -target.position.dot(&target.velocity) / target.velocity.norm_squared();
+        let t = time_to_go_quadratic(&target, &na::zero()).unwrap();
+        assert!((t - linear).abs() < 1e-9);
+
+        // Closing acceleration brings intercept forward; opening acceleration delays it
+        let closing = time_to_go_quadratic(&target, &na::Vector3::new(0.0, 0.0, -20.0)).unwrap();
+        assert!(closing < linear);
+        let r = target.position.z + target.velocity.z * closing - 10.0 * closing * closing;
+        assert!(r.abs() < 1e-6);
+        let opening = time_to_go_quadratic(&target, &na::Vector3::new(0.0, 0.0, 20.0)).unwrap();
+        assert!(opening > linear);
+
+        // Strong enough deceleration never reaches zero range; report the closest approach
+        let stopping = time_to_go_quadratic(&target, &na::Vector3::new(0.0, 0.0, 100.0)).unwrap();
+        assert!((stopping - 2.5).abs() < 1e-9);
+        let receding = Target {
+            position: target.position,
+            velocity: -target.velocity,
+        };
+        assert_eq!(time_to_go_quadratic(&receding, &na::zero()), None);
+        let arrived = Target {
+            position: na::zero(),
+            ..target
+        };
+        let accel = na::Vector3::new(0.0, 0.0, -20.0);
+        assert_eq!(time_to_go_quadratic(&arrived, &accel), Some(0.0));
+    }
+
+    #[test]
+    fn ipn_perpendicular() {
+        for i in 0..100 {
+            let t = f64::from(i);
+            let target = Target {
+                position: na::Vector3::new(t.sin() * 1e3, t.cos() * 10.0, -1e2 - t),
+                velocity: na::Vector3::new(t.cos() * 50.0, -t, 1e3),
+            };
+            if target.is_closing() {
+                ipn(3.0, &target);
+            }
+        }
+    }
+
+    #[test]
+    fn ipn_properties() {
+        use rand::SeedableRng;
+        fn check<N: RealField>(tolerance: f64) {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+            for _ in 0..10_000 {
+                let target = Target::<N>::arbitrary_closing(&mut rng);
+                assert!(target.is_closing());
+                let acceleration = ipn(na::convert(3.0), &target);
+                assert!(acceleration.iter().all(|x| x.is_finite()));
+                let scale = acceleration.norm() * target.velocity.norm();
+                assert!(acceleration.dot(&target.velocity).abs() <= scale * na::convert(tolerance));
+            }
+        }
+        check::<f32>(1e-5);
+        check::<f64>(1e-9);
+    }
+
+    #[test]
+    fn relative_target() {
+        let target = Target::relative(
+            &na::Point3::new(100.0f64, 0.0, 0.0),
+            &na::Vector3::new(0.0, 1000.0, 0.0),
+            &na::Point3::new(100.0, 3000.0, 0.0),
+            &na::Vector3::new(0.0, 200.0, 0.0),
+        );
+        assert_eq!(target.position, na::Vector3::new(0.0, 3000.0, 0.0));
+        assert_eq!(target.velocity, na::Vector3::new(0.0, -800.0, 0.0));
+        // Overtaking a slower target ahead
+        assert!(target.is_closing());
+        let outrun = Target::relative(
+            &na::Point3::origin(),
+            &na::Vector3::new(0.0, 100.0, 0.0),
+            &na::Point3::new(0.0, 3000.0, 0.0),
+            &na::Vector3::new(0.0, 200.0, 0.0),
+        );
+        assert!(!outrun.is_closing());
+    }
+
+    #[test]
+    fn closest() {
+        let crossing = Target {
+            position: na::Vector3::new(-3000.0f64, 4000.0, 500.0),
+            velocity: na::Vector3::new(300.0, -200.0, -20.0),
+        };
+        let (point, time) = closest_approach(&crossing);
+        assert_eq!(point, crossing.predict(time).position);
+        let sampled = (0..=100_000)
+            .map(|i| crossing.predict(i as f64 * 1e-3).position.norm())
+            .fold(f64::INFINITY, f64::min);
+        assert!(point.norm() <= sampled);
+        assert!(sampled - point.norm() < 1e-3);
+        let receding = Target {
+            velocity: -crossing.velocity,
+            ..crossing
+        };
+        assert_eq!(closest_approach(&receding), (receding.position, 0.0));
+    }
+
+    #[test]
+    fn delta_v() {
+        let velocity = na::Vector3::new(0.0f64, 500.0, 0.0);
+        // Stationary, dead ahead
+        let ahead = Target {
+            position: na::Vector3::new(0.0, 3000.0, 0.0),
+            velocity: -velocity,
+        };
+        assert!(required_delta_v(&ahead, &velocity, 500.0).unwrap() < 1e-9);
+        let offset = Target {
+            position: na::Vector3::new(1000.0, 3000.0, 0.0),
+            ..ahead
+        };
+        let (delta_v, _) = linear_steer(&offset, &velocity, 500.0).unwrap();
+        assert_eq!(required_delta_v(&offset, &velocity, 500.0), Some(delta_v.norm()));
+        assert!(delta_v.norm() > 100.0);
+    }
+
+    #[test]
+    fn steer_fov() {
+        let velocity = na::Vector3::new(0.0f64, 1000.0, 0.0);
+        // Fast crossing target, relative to the missile
+        let target = Target {
+            position: na::Vector3::new(2000.0, 4000.0, 0.0),
+            velocity: na::Vector3::new(-1000.0, -1000.0, 0.0),
+        };
+        let (delta_v, time) = linear_steer(&target, &velocity, 1000.0).unwrap();
+        let tight = linear_steer_fov(&target, &velocity, 1000.0, 10.0f64.to_radians()).unwrap();
+        assert_eq!((tight.0, tight.1), (delta_v, time));
+        assert!(!tight.2);
+        let wide = linear_steer_fov(&target, &velocity, 1000.0, 80.0f64.to_radians()).unwrap();
+        assert!(wide.2);
+    }
+
+    #[test]
+    fn try_ipn_closing() {
+        let closing = Target {
+            position: na::Vector3::new(100.0f64, 2000.0, 0.0),
+            velocity: na::Vector3::new(0.0, -300.0, 0.0),
+        };
+        assert_eq!(try_ipn(3.0, &closing), Some(ipn(3.0, &closing)));
+        let receding = Target {
+            velocity: -closing.velocity,
+            ..closing
+        };
+        assert_eq!(try_ipn(3.0, &receding), None);
+        let coincident = Target {
+            position: na::Vector3::new(0.0, 1e-9, 0.0),
+            ..closing
+        };
+        assert!(coincident.is_closing());
+        assert_eq!(try_ipn(3.0, &coincident), None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "perpendicular")]
+    fn ipn_perpendicular_corrupted() {
+        let velocity = na::Vector3::new(0.0, 1.0, -1.0);
+        let corrupted = na::Vector3::new(0.0, 1.0, 0.0);
+        debug_assert_perpendicular(&corrupted, &velocity);
+    }
+
+    #[test]
+    fn pn_variants() {
+        let head_on = Target {
+            position: na::Vector3::new(0.0, 0.0, -10.0),
+            velocity: na::Vector3::new(0.0, 0.0, 1.0),
+        };
+        let behind = Target {
+            position: na::Vector3::new(0.0, 0.0, 10.0),
+            velocity: na::Vector3::new(0.0, 1.0, -1.0),
+        };
+        for &target in &[head_on, behind] {
+            assert!(miss_with(target, |t| ipn(3.0, t)) < 1.0);
+            assert!(miss_with(target, |t| tpn(3.0, t)) < 1.0);
+            assert!(miss_with(target, |t| rtpn(3.0, t)) < 1.0);
+        }
+
+        let (i, t, r) = (ipn(3.0, &behind), tpn(3.0, &behind), rtpn(3.0, &behind));
+        assert!((i - t).norm() > 1e-2 && (i - r).norm() > 1e-2 && (t - r).norm() > 1e-2);
+        assert!(t.dot(&behind.position).abs() < 1e-9);
+        assert!(r.dot(&behind.velocity).abs() < 1e-9);
+        assert!(r.norm() < i.norm());
+
+        // Crossing the line of sight nearly as fast as it closes, where the laws differ most
+        let crossing = Target {
+            position: na::Vector3::new(0.0, 5000.0, 0.0),
+            velocity: na::Vector3::new(-400.0, -500.0, 0.0),
+        };
+        // Within one step's travel of `miss_with`
+        let step = crossing.velocity.norm() * 1e-2;
+        assert!(miss_with(crossing, |t| ipn(3.0, t)) < step);
+        assert!(miss_with(crossing, |t| tpn(3.0, t)) < step);
+        assert!((ipn(3.0, &crossing) - tpn(3.0, &crossing)).norm() > 1.0);
+
+        // Only `ipn` can raise a low initial closing speed
+        let deflection = Target {
+            position: na::Vector3::new(0.0, -1.0, -10.0),
+            velocity: na::Vector3::new(0.0, 1.0, 0.1),
+        };
+        assert!(miss_with(deflection, |t| ipn(3.0, t)) < 1.0);
+        assert!(miss_with(deflection, |t| tpn(3.0, t)) > 1.0);
+    }
+
+    #[test]
+    fn retro() {
+        fn miss(law: impl Fn(&Target<f64>) -> na::Vector3<f64>) -> f64 {
+            const TIMESTEP: f64 = 1e-3;
+            let heading = 70.0f64.to_radians();
+            let mut missile = FixedWingInterceptor::new(
+                na::Point3::origin(),
+                na::Vector3::new(heading.sin(), heading.cos(), 0.0) * 600.0,
+                0.0,
+            );
+            let mut target = (na::Point3::new(0.0, 3000.0, 0.0), na::Vector3::y() * 300.0);
+            let mut closest = f64::INFINITY;
+            for _ in 0..60_000 {
+                let relative = Target {
+                    position: target.0 - missile.position,
+                    velocity: target.1 - missile.velocity,
+                };
+                closest = closest.min(relative.position.norm());
+                missile.step(&law(&relative), TIMESTEP);
+                target.0 += target.1 * TIMESTEP;
+            }
+            closest
+        }
+        // Turned 70 degrees away from a target receding at half speed
+        let heading = 70.0f64.to_radians();
+        let opening = Target {
+            position: na::Vector3::new(0.0, 3000.0, 0.0),
+            velocity: na::Vector3::y() * 300.0
+                - na::Vector3::new(heading.sin(), heading.cos(), 0.0) * 600.0,
+        };
+        assert!(!opening.is_closing());
+        assert!(miss(|t| retro_pn(3.0, t)) < 1.0);
+        // `ipn` assumes a closing target, so it has no command to give
+        assert!(try_ipn(3.0, &opening).is_none());
+        if cfg!(debug_assertions) {
+            assert!(std::panic::catch_unwind(|| ipn(3.0, &opening)).is_err());
+        }
+        assert_eq!(miss(|t| try_ipn(3.0, t).unwrap_or_else(na::zero)), 3000.0);
+        // Without the retro term, scaling by the magnitude of the closing speed never gets the
+        // range to close
+        let tpn_magnitude = |t: &Target<f64>| {
+            los_rate(t).cross(&t.position.normalize()) * (3.0 * closing_speed(t).abs())
+        };
+        assert_eq!(miss(tpn_magnitude), 3000.0);
+        // Identical to `ipn` once closing
+        let closing = Target::<f64> {
+            position: na::Vector3::new(0.0, 3000.0, 0.0),
+            velocity: na::Vector3::new(50.0, -600.0, 0.0),
+        };
+        assert!((retro_pn(3.0, &closing) - ipn(3.0, &closing)).norm() < 1e-12);
+    }
+
+    #[test]
+    fn los_kinematics() {
+        // Launched to meet at `point` after 10 seconds
+        let point = na::Vector3::new(4000.0f64, 1500.0, 300.0);
+        let target_start = na::Vector3::new(6000.0, -2000.0, 1000.0);
+        let collision = Target {
+            position: target_start,
+            velocity: (point - target_start) / 10.0 - point / 10.0,
+        };
+        assert!(los_rate(&collision).norm() < 1e-12);
+        assert!((closing_speed(&collision) - target_start.norm() / 10.0).abs() < 1e-9);
+
+        let crossing = Target {
+            position: na::Vector3::new(0.0, 1000.0, 0.0),
+            velocity: na::Vector3::new(50.0, -200.0, 0.0),
+        };
+        // The bearing from the y axis towards x grows at 50 / 1000 rad/s
+        assert!((los_rate(&crossing) - na::Vector3::new(0.0, 0.0, -0.05)).norm() < 1e-12);
+        assert_eq!(closing_speed(&crossing), 200.0);
+        let receding = Target {
+            velocity: -crossing.velocity,
+            ..crossing
+        };
+        assert!(closing_speed(&receding) < 0.0 && !receding.is_closing());
+
+        assert!(is_collision_course(&collision, 1e-6));
+        let head_on = Target {
+            position: na::Vector3::new(0.0, 1000.0, 0.0),
+            velocity: na::Vector3::new(0.0, -200.0, 0.0),
+        };
+        assert!(is_collision_course(&head_on, 1e-6));
+        assert!(!is_collision_course(&crossing, 1e-3));
+        assert!(!is_collision_course(&receding, 1.0));
+    }
+
+    #[test]
+    fn parallel() {
+        const TIMESTEP: f64 = 1e-3;
+        let mut target = Target::<f64> {
+            position: na::Vector3::new(0.0, -100.0, -1000.0),
+            velocity: na::Vector3::new(5.0, 20.0, 300.0),
+        };
+        let initial = target.position;
+        // Residual drift is bounded by the initial LOS rate over the gain
+        let gain = 20.0;
+        let bound = los_rate(&target).norm() / gain;
+        while target.is_closing() && target.position.norm() > 1.0 {
+            target.velocity -= parallel_navigation(&target, gain) * TIMESTEP;
+            target.position += target.velocity * TIMESTEP;
+            assert!(target.position.angle(&initial) < bound * 1.1);
+        }
+        assert!(target.position.norm() < 1.0);
+    }
+
+    #[test]
+    fn augmented() {
+        fn miss(law: impl Fn(&ManeuveringTarget<f64>) -> na::Vector3<f64>) -> f64 {
+            const TIMESTEP: f64 = 1e-2;
+            let mut target = ManeuveringTarget {
+                position: na::Vector3::new(0.0, 0.0, -100.0),
+                velocity: na::Vector3::new(0.0, 0.0, 10.0),
+                acceleration: na::Vector3::new(0.5, 0.0, 0.0),
+            };
+            while target.tangent().is_closing() {
+                let acceleration = law(&target);
+                target.velocity += TIMESTEP * (target.acceleration - acceleration);
+                target.position += TIMESTEP * target.velocity;
+            }
+            target.position.norm()
+        }
+        let augmented = miss(|t| apn(3.0, t));
+        let ideal = miss(|t| ipn(3.0, &t.tangent()));
+        assert!(augmented < ideal, "{} vs {}", augmented, ideal);
+
+        let unaccelerated = ManeuveringTarget {
+            position: na::Vector3::new(0.0, -1.0, -10.0),
+            velocity: na::Vector3::new(0.0, 1.0, 0.1),
+            acceleration: na::zero(),
+        };
+        assert_eq!(apn(3.0, &unaccelerated), ipn(3.0, &unaccelerated.tangent()));
+    }
+
+    #[test]
+    fn biased() {
+        const TIMESTEP: f64 = 1e-3;
+        let initial = Target {
+            position: na::Vector3::new(3000.0, 200.0, 0.0),
+            velocity: na::Vector3::new(-300.0, 0.0, 0.0),
+        };
+        // Strike with the relative velocity 20 degrees off the initial heading
+        let angle = 20.0f64.to_radians();
+        let desired = na::Unit::new_normalize(na::Vector3::new(-angle.cos(), angle.sin(), 0.0));
+        let engage = |law: &dyn Fn(&Target<f64>) -> na::Vector3<f64>| {
+            let mut target = initial;
+            while target.is_closing() && target.position.norm() > 1.0 {
+                target.velocity -= law(&target) * TIMESTEP;
+                target.position += target.velocity * TIMESTEP;
+            }
+            target
+        };
+        let biased = engage(&|t| bpn(6.0, t, &desired, time_to_go(t)));
+        assert!(biased.position.norm() < 2.0);
+        assert!(biased.velocity.angle(&desired) < 1.0f64.to_radians());
+        let plain = engage(&|t| ipn(6.0, t));
+        assert!(plain.velocity.angle(&desired) > 10.0f64.to_radians());
+
+        let far = bpn(6.0, &initial, &desired, 1e9);
+        assert!((far - ipn(6.0, &initial)).norm() < 1e-6);
+        assert_eq!(bpn(6.0, &initial, &desired, 0.0), na::zero());
+    }
+
+    #[test]
+    fn model_predictive() {
+        fn miss(law: impl Fn(&ManeuveringTarget<f64>) -> na::Vector3<f64>) -> f64 {
+            const TIMESTEP: f64 = 1e-2;
+            let mut target = ManeuveringTarget {
+                position: na::Vector3::new(0.0, 0.0, -100.0),
+                velocity: na::Vector3::new(0.0, 0.0, 10.0),
+                acceleration: na::Vector3::new(1.0, 0.0, 0.0),
+            };
+            let mut closest = target.position.norm();
+            while target.tangent().is_closing() {
+                let acceleration = law(&target);
+                target.velocity += TIMESTEP * (target.acceleration - acceleration);
+                target.position += TIMESTEP * target.velocity;
+                closest = closest.min(target.position.norm());
+            }
+            closest
+        }
+        // Chasing the maneuver saturates `ipn`, while lookahead anticipates it
+        let predictive = miss(|t| mpc_guidance(t, 4.0, 8, 2.0));
+        let ideal = miss(|t| clamp_acceleration(ipn(3.0, &t.tangent()), 2.0));
+        assert!(predictive < ideal, "{} vs {}", predictive, ideal);
+    }
+
+    #[test]
+    fn planar_constrained() {
+        const TIMESTEP: f64 = 1e-3;
+        let normal = na::Vector3::<f64>::x_axis();
+        let miss = |offset: f64| {
+            let mut missile_velocity = na::Vector3::new(0.0, 0.0, -100.0);
+            let mut target = Target {
+                position: na::Vector3::new(offset, 50.0, -1000.0),
+                velocity: na::Vector3::new(0.0, 5.0, 100.0),
+            };
+            let mut closest = target.position.norm();
+            while target.is_closing() {
+                let (command, out_of_plane) =
+                    planar_constrained_ipn(3.0, &target, &missile_velocity, &normal);
+                assert!(command.dot(&normal).abs() < 1e-9);
+                assert!((command + out_of_plane - ipn(3.0, &target)).norm() < 1e-9);
+                missile_velocity += command * TIMESTEP;
+                target.velocity -= command * TIMESTEP;
+                target.position += target.velocity * TIMESTEP;
+                closest = closest.min(target.position.norm());
+            }
+            closest
+        };
+        // In-plane geometry is unaffected, while out-of-plane offsets pass through as miss
+        assert!(miss(0.0) < 1.0);
+        for &offset in &[5.0, 20.0] {
+            assert!((miss(offset) - offset).abs() < 1.0, "{}", miss(offset));
+        }
+    }
+
+    #[test]
+    fn pursuit() {
+        /// Miss distance and peak acceleration
+        fn fly(law: impl Fn(&Target<f64>, &na::Vector3<f64>) -> na::Vector3<f64>) -> (f64, f64) {
+            const TIMESTEP: f64 = 1e-3;
+            let mut velocity = na::Vector3::new(0.0, 0.0, -300.0);
+            // Crossing at right angles
+            let target_velocity = na::Vector3::new(100.0, 0.0, 0.0);
+            let mut target = Target {
+                position: na::Vector3::new(0.0, 0.0, -3000.0),
+                velocity: target_velocity - velocity,
+            };
+            let mut closest = target.position.norm();
+            let mut peak = 0.0f64;
+            while target.is_closing() {
+                let acceleration = law(&target, &velocity);
+                assert!(acceleration.dot(&velocity).abs() < 1e-6 * velocity.norm());
+                peak = peak.max(acceleration.norm());
+                velocity += acceleration * TIMESTEP;
+                target.velocity = target_velocity - velocity;
+                target.position += target.velocity * TIMESTEP;
+                closest = closest.min(target.position.norm());
+            }
+            (closest, peak)
+        }
+        let (pure_miss, pure_peak) = fly(pure_pursuit);
+        let (lead_miss, lead_peak) = fly(lead_pursuit);
+        assert!(pure_miss < 1.0 && lead_miss < 1.0);
+        // The tail chase demands much harder turns
+        assert!(pure_peak > 2.0 * lead_peak, "{} vs {}", pure_peak, lead_peak);
+
+        // Lead pursuit flies straight at a constant-velocity target
+        let solution = linear_aim(
+            &Target {
+                position: na::Vector3::new(0.0, 0.0, -3000.0),
+                velocity: na::Vector3::new(100.0, 0.0, 0.0),
+            },
+            300.0,
+        )
+        .unwrap();
+        let on_course = solution.direction.into_inner() * 300.0;
+        let target = Target {
+            position: na::Vector3::new(0.0, 0.0, -3000.0),
+            velocity: na::Vector3::new(100.0, 0.0, 0.0) - on_course,
+        };
+        assert!(lead_pursuit(&target, &on_course).norm() < 1e-6);
+        assert!(pure_pursuit(&target, &on_course).norm() > 1.0);
+        assert_eq!(pure_pursuit(&target, &na::zero()), na::zero());
+    }
+
+    #[test]
+    fn beam_riding() {
+        let target = na::Vector3::new(8000.0f64, 2000.0, 0.0);
+        let beam = target.normalize();
+        let mut position = na::Vector3::new(200.0, 100.0, 30.0);
+        let mut velocity = beam * 400.0;
+        let offset = |p: &na::Vector3<f64>| (p - beam * beam.dot(p)).norm();
+        let initial = clos_guidance(&target, &position, &velocity, 1.0, 2.0);
+        assert!(initial.dot(&beam).abs() < 1e-9);
+        assert!(initial.dot(&(position - beam * beam.dot(&position))) < 0.0);
+        let start = offset(&position);
+        for _ in 0..10_000 {
+            velocity += clos_guidance(&target, &position, &velocity, 1.0, 2.0) * 1e-3;
+            position += velocity * 1e-3;
+        }
+        assert!(offset(&position) < start * 1e-2);
+    }
+
+    #[test]
+    fn avoidance() {
+        const TIMESTEP: f64 = 1e-3;
+        const RADIUS: f64 = 300.0;
+        let mut target = Target::<f64> {
+            position: na::Vector3::new(0.0, 0.0, -1000.0),
+            velocity: na::Vector3::new(0.0, 0.0, 100.0),
+        };
+        // Stationary, directly between us and the target
+        let mut obstacle = Target {
+            position: na::Vector3::new(0.0, 0.0, -500.0),
+            ..target
+        };
+        let mut clearance = f64::INFINITY;
+        while target.is_closing() {
+            let obstacles = [obstacle];
+            let a = avoidance_ipn(3.0, &target, &obstacles, RADIUS, 200.0);
+            for state in &mut [&mut target, &mut obstacle] {
+                state.velocity -= a * TIMESTEP;
+                state.position += state.velocity * TIMESTEP;
+            }
+            clearance = clearance.min(obstacle.position.norm());
+        }
+        // Plain `ipn` would fly straight through it
+        assert!(clearance > 50.0, "{}", clearance);
+        assert!(target.position.norm() < 1.0, "{}", target.position.norm());
+    }
+
+    #[test]
+    fn null_zem() {
+        const STEPS: usize = 1000;
+        let mut target = Target::<f64> {
+            position: na::Vector3::new(30.0, -20.0, 1000.0),
+            velocity: na::Vector3::new(2.0, 1.0, -100.0),
+        };
+        let time_to_go = 10.0;
+        let zem = target.position + target.velocity * time_to_go;
+        let acceleration = accel_to_null_zem(&zem, time_to_go);
+        let dt = time_to_go / STEPS as f64;
+        for _ in 0..STEPS {
+            target.position += target.velocity * dt - acceleration * (0.5 * dt * dt);
+            target.velocity -= acceleration * dt;
+        }
+        assert!(target.position.norm() < 1e-9);
+        assert_eq!(accel_to_null_zem(&zem, 0.0), na::zero());
+    }
+
+    #[test]
+    fn gravity_compensated() {
+        const TIMESTEP: f64 = 1e-3;
+        let gravity = na::Vector3::new(0.0, 0.0, -9.81);
+        let initial = Target::<f64> {
+            position: na::Vector3::new(200.0, 12_000.0, 1000.0),
+            velocity: na::Vector3::new(0.0, -400.0, 0.0),
+        };
+        // Under two gravities of authority
+        let miss = |law: &dyn Fn(&Target<f64>) -> na::Vector3<f64>| {
+            let mut target = initial;
+            while target.is_closing() {
+                let command = clamp_acceleration(law(&target), 20.0);
+                target.velocity -= (command + gravity) * TIMESTEP;
+                target.position += target.velocity * TIMESTEP;
+            }
+            target.position.norm()
+        };
+        let optimal = miss(&|t| optimal_guidance(t, time_to_go(t), gravity));
+        let uncompensated = miss(&|t| ipn(3.0, t));
+        assert!(optimal < 1.0);
+        // PN only responds to gravity drop once it shows up in the LOS rate, by which point more
+        // than the available acceleration is needed
+        assert!(uncompensated > 10.0);
+
+        let t_go = time_to_go(&initial);
+        assert_eq!(
+            optimal_guidance(&initial, t_go, na::zero()),
+            zem_guidance(3.0, &initial, t_go)
+        );
+    }
+
+    #[test]
+    fn clamp() {
+        let limited = clamp_acceleration(na::Vector3::new(300.0f64, -400.0, 0.0), 100.0);
+        assert!((limited.norm() - 100.0).abs() < 1e-12);
+        assert!((limited - na::Vector3::new(60.0, -80.0, 0.0)).norm() < 1e-12);
+        let small = na::Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(clamp_acceleration(small, 100.0), small);
+        assert_eq!(clamp_acceleration(na::zero(), 100.0), na::Vector3::zeros());
+        assert_eq!(clamp_acceleration(small, 0.0), na::Vector3::zeros());
+    }
+
+    #[test]
+    fn zem_pn() {
+        let target = Target::<f64> {
+            position: na::Vector3::new(100.0, -50.0, -5000.0),
+            velocity: na::Vector3::new(-5.0, 10.0, 500.0),
+        };
+        let t_go = time_to_go(&target);
+        assert!(zem(&target, t_go).dot(&target.velocity).abs() < 1e-6);
+        let by_zem = zem_guidance(3.0, &target, t_go);
+        let by_ipn = ipn(3.0, &target);
+        // The forms differ at the order of the heading error, here a few percent
+        assert!((by_zem - by_ipn).norm() < 5e-2 * by_ipn.norm());
+        // Within one step's travel of `miss_with`
+        let miss = miss_with(target, |t| zem_guidance(3.0, t, time_to_go(t)));
+        assert!(miss < target.velocity.norm() * 1e-2);
+        assert_eq!(zem_guidance(3.0, &target, 0.0), na::zero());
+    }
+
+    #[test]
+    fn weave() {
+        const FREQUENCY: f64 = 2.0;
+        const AMPLITUDE: f64 = 100.0;
+        fn weave(t: f64) -> na::Vector3<f64> {
+            na::Vector3::y() * (AMPLITUDE * (FREQUENCY * t).sin())
+        }
+        fn miss(law: impl Fn(&Target<f64>, f64, f64) -> na::Vector3<f64>) -> f64 {
+            const TIMESTEP: f64 = 1e-3;
+            // Flight control system time constant
+            const LAG: f64 = 0.5;
+            let mut target = Target {
+                position: na::Vector3::new(0.0, 0.0, 8000.0),
+                velocity: na::Vector3::new(0.0, 50.0, -1000.0),
+            };
+            let mut achieved = na::Vector3::zeros();
+            let mut t = 0.0;
+            while target.is_closing() {
+                let time_to_go =
+                    -target.position.dot(&target.velocity) / target.velocity.norm_squared();
+                achieved += (law(&target, t, time_to_go) - achieved) * (TIMESTEP / LAG);
+                target.velocity += TIMESTEP * (weave(t) - achieved);
+                target.position += TIMESTEP * target.velocity;
+                t += TIMESTEP;
+            }
+            target.position.norm()
+        }
+        let optimal = miss(|target, t, time_to_go| {
+            let jerk = na::Vector3::y() * (AMPLITUDE * FREQUENCY * (FREQUENCY * t).cos());
+            weave_optimal_guidance(target, &weave(t), &jerk, FREQUENCY, time_to_go, 3.0)
+        });
+        // Augmented proportional navigation, assuming the current target acceleration persists
+        let augmented = miss(|target, t, time_to_go| {
+            let zem = target.position
+                + target.velocity * time_to_go
+                + weave(t) * (0.5 * time_to_go * time_to_go);
+            let los = target.position.normalize();
+            (zem - los * los.dot(&zem)) * (3.0 / (time_to_go * time_to_go))
+        });
+        assert!(optimal < 1.0, "optimal: {}", optimal);
+        assert!(
+            optimal < augmented,
+            "optimal: {}; augmented: {}",
+            optimal,
+            augmented
+        );
+
+        // A target that isn't weaving matches the limit of slow weaves
+        let target = Target::<f64> {
+            position: na::Vector3::new(100.0, 200.0, 4000.0),
+            velocity: na::Vector3::new(0.0, 0.0, -400.0),
+        };
+        let (accel, jerk) = (na::Vector3::new(0.0, 30.0, 0.0), na::Vector3::new(5.0, 0.0, 0.0));
+        let steady = weave_optimal_guidance(&target, &accel, &jerk, 0.0, 10.0, 3.0);
+        assert!(steady.iter().all(|x| x.is_finite()));
+        let slow = weave_optimal_guidance(&target, &accel, &jerk, 1e-3, 10.0, 3.0);
+        assert!((steady - slow).norm() < 1e-4 * steady.norm());
+        let unaccelerated =
+            weave_optimal_guidance(&target, &na::zero(), &na::zero(), 0.0, 10.0, 3.0);
+        assert!((unaccelerated - optimal_guidance(&target, 10.0, na::zero())).norm() < 1e-12);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let target = Target {
+            position: na::Vector3::new(0.1, -1.0 / 3.0, 1e-300),
+            velocity: na::Vector3::new(f64::MAX, -0.0, 299_792_458.0),
+        };
+        let json = serde_json::to_string(&target).unwrap();
+        let parsed = serde_json::from_str::<Target<f64>>(&json).unwrap();
+        assert_eq!(parsed.position, target.position);
+        assert_eq!(parsed.velocity, target.velocity);
+        let target = Target {
+            position: na::Vector3::new(0.1f32, -1.0 / 3.0, 1e-30),
+            velocity: na::Vector3::new(f32::MAX, 7.0e-3, -42.0),
+        };
+        let json = serde_json::to_string(&target).unwrap();
+        let parsed = serde_json::from_str::<Target<f32>>(&json).unwrap();
+        assert_eq!(parsed.position, target.position);
+        assert_eq!(parsed.velocity, target.velocity);
+    }
+
+    #[test]
+    fn angular_momentum() {
+        let head_on = Target {
+            position: na::Vector3::new(0.0, 0.0, -10.0),
+            velocity: na::Vector3::new(0.0, 0.0, 2.0),
+        };
+        assert_eq!(head_on.angular_momentum(), na::zero());
+        let crossing = Target::<f64> {
+            position: na::Vector3::new(0.0, 5.0, -10.0),
+            velocity: na::Vector3::new(0.0, 0.0, 2.0),
+        };
+        let h = crossing.angular_momentum();
+        assert!(h.norm() > 0.0);
+        assert!(h.dot(&crossing.position).abs() < 1e-12);
+        assert!(h.dot(&crossing.velocity).abs() < 1e-12);
+        // Miss distance of the unaccelerated target
+        assert!((h.norm() / crossing.velocity.norm() - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn scaling() {
+        // Kilometers and minutes to meters and seconds
+        let target = Target::<f64> {
+            position: na::Vector3::new(3.0, -1.0, 12.0),
+            velocity: na::Vector3::new(0.5, 2.0, -30.0),
+        };
+        let scaled = target.scaled(1000.0, 60.0);
+        assert_eq!(scaled.position, na::Vector3::new(3000.0, -1000.0, 12000.0));
+        let expected = na::Vector3::new(500.0 / 60.0, 2000.0 / 60.0, -500.0);
+        assert!((scaled.velocity - expected).norm() < 1e-12);
+        assert_eq!(scaled.is_closing(), target.is_closing());
+        let receding = Target {
+            position: target.position,
+            velocity: -target.velocity,
+        };
+        assert_eq!(receding.scaled(1e-3, 7.0).is_closing(), receding.is_closing());
+
+        let unscaled = scaled.scaled(1e-3, 1.0 / 60.0);
+        assert!((unscaled.position - target.position).norm() < 1e-12);
+        assert!((unscaled.velocity - target.velocity).norm() < 1e-12);
+    }
+
+    #[test]
+    fn predicted_pn_miss() {
+        const TIMESTEP: f64 = 1e-4;
+        let scenarios = [
+            Target {
+                position: na::Vector3::new(0.0, 0.0, -10.0),
+                velocity: na::Vector3::new(0.0, 0.0, 1.0),
+            },
+            Target {
+                position: na::Vector3::new(0.0, -1.0, -10.0),
+                velocity: na::Vector3::new(0.0, 1.0, 0.1),
+            },
+            Target {
+                position: na::Vector3::new(0.0, 0.0, 10.0),
+                velocity: na::Vector3::new(0.0, 1.0, -1.0),
+            },
+        ];
+        for &n in &[0.0, 0.5, 3.0] {
+            for &initial in &scenarios {
+                let mut target = initial;
+                let mut closest = target.position.norm();
+                while target.is_closing() {
+                    target.velocity -= ipn(n, &target) * TIMESTEP;
+                    target.position += target.velocity * TIMESTEP;
+                    closest = closest.min(target.position.norm());
+                }
+                let predicted = predicted_miss(&initial, n);
+                assert!((predicted - closest).abs() < 1e-2, "{} vs {}", predicted, closest);
+            }
+        }
+    }
+
+    #[test]
+    fn predicted_miss_heading_error() {
+        // Two degrees of heading error at 5 km
+        let error = 2.0f64.to_radians();
+        let target = Target {
+            position: na::Vector3::new(0.0, 5000.0, 0.0),
+            velocity: na::Vector3::new(error.sin(), -error.cos(), 0.0) * 100.0,
+        };
+        assert_eq!(predicted_miss(&target, 3.0), 0.0);
+        assert!(miss(target) < 1.0);
+        let predicted = predicted_miss(&target, 0.5);
+        let integrated = miss_with(target, |target| ipn(0.5, target));
+        assert!(predicted > 1.0);
+        assert!(integrated / predicted > 0.5 && integrated / predicted < 2.0);
+    }
+
+    #[test]
+    fn predicted_miss_launch() {
+        const TIMESTEP: f64 = 1e-3;
+        // Crossing target, seen from a launcher at rest
+        let target = Target {
+            position: na::Vector3::new(0.0, 3000.0, 0.0),
+            velocity: na::Vector3::new(200.0, 50.0, 0.0),
+        };
+        let fly = |n: f64, speed: f64| {
+            let mut relative = Target {
+                position: target.position,
+                velocity: target.velocity - target.position.normalize() * speed,
+            };
+            let mut closest = relative.position.norm();
+            while relative.is_closing() {
+                relative.velocity -= ipn(n, &relative) * TIMESTEP;
+                relative.position += relative.velocity * TIMESTEP;
+                closest = closest.min(relative.position.norm());
+            }
+            closest
+        };
+        // A faster launch leaves less heading error to grow
+        let slow = predicted_miss_pn(0.5, &target, 300.0);
+        let fast = predicted_miss_pn(0.5, &target, 900.0);
+        assert!(fast < slow, "{} {}", fast, slow);
+        for &(predicted, speed) in &[(slow, 300.0), (fast, 900.0)] {
+            let simulated = fly(0.5, speed);
+            assert!((predicted - simulated).abs() < 1.0, "{} vs {}", predicted, simulated);
+        }
+        assert_eq!(predicted_miss_pn(3.0, &target, 300.0), 0.0);
+        assert!(fly(3.0, 300.0) < 1.0);
+        // Too slow to close on a receding target
+        assert_eq!(predicted_miss_pn(3.0, &target, 30.0), 3000.0);
+        let at_launcher = Target {
+            position: na::zero(),
+            ..target
+        };
+        assert_eq!(predicted_miss_pn(3.0, &at_launcher, 300.0), 0.0);
+    }
+
+    /// `linear_aim` as originally written, selecting the root with iterator adapters
+    fn reference_linear_aim(
+        target: &Target<f64>,
+        speed: f64,
+    ) -> Option<(na::Unit<na::Vector3<f64>>, f64)> {
+        let a = target.velocity.norm_squared() - (speed * speed);
+        if a.abs() < 1e-3 {
+            return Some((na::Unit::new_normalize(target.position), 0.0));
+        }
+        let b = 2. * target.position.dot(&target.velocity);
+        let c = target.position.norm_squared();
+        let rt = Some(b * b - 4. * a * c).filter(|&d| d >= 0.0)?.sqrt();
+        let t0 = (-b + rt) / (2. * a);
+        let t1 = (-b - rt) / (2. * a);
+        let t = [t0, t1]
+            .iter()
+            .cloned()
+            .filter(|&x| x >= 0.0)
+            .min_by(|x, y| x.partial_cmp(y).unwrap())?;
+        let pos = target.position + target.velocity * t;
+        Some((na::Unit::new_normalize(pos), t))
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn aim_solution_fields() {
+        let target = Target {
+            position: na::Vector3::new(2000.0, 500.0, 0.0),
+            velocity: na::Vector3::new(-150.0, 30.0, 10.0),
+        };
+        let solution = linear_aim(&target, 700.0).unwrap();
+        let (direction, time) = linear_aim_tuple(&target, 700.0).unwrap();
+        assert_eq!(solution.direction, direction);
+        assert_eq!(solution.time, time);
+        assert_eq!(<(_, _)>::from(solution), (direction, time));
+        assert_eq!(solution.required_speed, 700.0);
+        assert!((solution.point - direction.into_inner() * 700.0 * time).norm() < 1e-9);
+        assert!((solution.point - (target.position + target.velocity * time)).norm() < 1e-9);
+    }
+
+    #[test]
+    fn all_solutions() {
+        // Faster than the projectile, crossing ahead of it
+        let crossing = Target::<f64> {
+            position: na::Vector3::new(-2000.0, 1000.0, 0.0),
+            velocity: na::Vector3::new(600.0, 0.0, 0.0),
+        };
+        let [early, late] = linear_aim_solutions(&crossing, 400.0);
+        let (early, late) = (early.unwrap(), late.unwrap());
+        assert!(0.0 < early.time && early.time < late.time);
+        for solution in &[early, late] {
+            let miss = solution.direction.into_inner() * 400.0 * solution.time - solution.point;
+            assert!(miss.norm() < 1e-9);
+        }
+        assert_eq!(linear_aim(&crossing, 400.0), Some(early));
+
+        // A faster projectile has exactly one intercept
+        let [only, none] = linear_aim_solutions(&crossing, 800.0);
+        assert_eq!(only, linear_aim(&crossing, 800.0));
+        assert!(only.is_some());
+        assert_eq!(none, None);
+
+        // Matched speeds use the degenerate solution
+        let matched = Target {
+            position: na::Vector3::new(0.0, 1000.0, 0.0),
+            velocity: na::Vector3::new(400.0, 0.0, 0.0),
+        };
+        let solutions = linear_aim_solutions(&matched, 400.0);
+        assert_eq!(solutions, [linear_aim(&matched, 400.0), None]);
+        assert_eq!(solutions[0].unwrap().time, 0.0);
+
+        let receding = Target {
+            position: na::Vector3::new(0.0, 1000.0, 0.0),
+            velocity: na::Vector3::new(0.0, 600.0, 0.0),
+        };
+        assert_eq!(linear_aim_solutions(&receding, 400.0), [None, None]);
+    }
+
+    #[test]
+    fn degenerate_inputs() {
+        let target = Target {
+            position: na::Vector3::new(100.0f64, 2000.0, 0.0),
+            velocity: na::Vector3::new(0.0, -300.0, 0.0),
+        };
+        let coincident = Target {
+            position: na::zero(),
+            ..target
+        };
+        assert_eq!(linear_aim(&coincident, 500.0), None);
+        let nearly = Target {
+            position: na::Vector3::new(0.0, 1e-300, 0.0),
+            ..target
+        };
+        assert_eq!(linear_aim(&nearly, 500.0), None);
+        // Each would otherwise yield a NaN discriminant or root
+        let nan = Target {
+            velocity: na::Vector3::new(f64::NAN, -300.0, 0.0),
+            ..target
+        };
+        assert_eq!(linear_aim(&nan, 500.0), None);
+        let infinite = Target {
+            velocity: na::Vector3::new(f64::INFINITY, -300.0, 0.0),
+            ..target
+        };
+        assert_eq!(linear_aim(&infinite, 500.0), None);
+        assert_eq!(linear_aim(&target, f64::NAN), None);
+        assert_eq!(linear_aim_solutions(&nan, 500.0), [None, None]);
+    }
+
+    #[test]
+    fn default_tolerances() {
+        fn check<N: RealField>(precision: f64) {
+            let n = |x: f64| na::convert::<_, N>(x);
+            let vector = |x, y| na::Vector3::new(n(x), n(y), N::zero());
+            // Slow but distinct speeds aren't mistaken for a degeneracy
+            let slow = Target {
+                position: vector(0.0, 50.0),
+                velocity: vector(0.3, 0.0),
+            };
+            let solution = linear_aim(&slow, n(0.301)).unwrap();
+            let flown = n(0.301) * solution.time;
+            assert!((solution.point.norm() - flown).abs() < flown * n(precision));
+            assert!(solution.time > n(2000.0));
+            // Matched speeds are
+            let matched = linear_aim(&slow, n(0.3)).unwrap();
+            assert_eq!(matched.time, N::zero());
+            assert_eq!(matched.point, slow.position);
+            assert_eq!(matched.direction.into_inner(), slow.position.normalize());
+            // A grazing intercept, with a discriminant of zero before rounding
+            let grazing = Target {
+                position: vector(-0.4, 0.3),
+                velocity: vector(0.5, 0.0),
+            };
+            let solution = linear_aim(&grazing, n(0.3)).unwrap();
+            // Rounding the discriminant perturbs a double root by its square root
+            let sensitivity = N::default_epsilon().sqrt() * n(10.0);
+            assert!((solution.time - n(1.25)).abs() < sensitivity);
+            let coincident = Target {
+                position: na::zero(),
+                velocity: vector(0.5, 0.0),
+            };
+            assert_eq!(linear_aim_solutions(&coincident, n(0.3)), [None, None]);
+        }
+        check::<f32>(1e-3);
+        check::<f64>(1e-9);
+    }
+
+    #[test]
+    fn degeneracy_scale() {
+        for &scale in &[1e-3f32, 1e3] {
+            let target = Target {
+                position: na::Vector3::new(0.0, 1000.0, 0.0) * scale,
+                velocity: na::Vector3::new(1.0, 0.0, 0.0) * scale,
+            };
+            let speed = 1.01 * scale;
+            let solution = linear_aim(&target, speed).unwrap();
+            assert!(solution.time > 0.0);
+            let flown = speed * solution.time;
+            assert!((solution.point.norm() - flown).abs() < flown * 1e-4);
+            assert_eq!(linear_aim_eps(&target, speed, 1e-3), Some(solution));
+            // A coarse threshold takes the degenerate branch
+            assert_eq!(linear_aim_eps(&target, speed, 0.1).unwrap().time, 0.0);
+            assert_eq!(linear_aim(&target, scale).unwrap().time, 0.0);
+        }
+    }
+
+    #[test]
+    fn linear_aim_matches_reference() {
+        use rand::{distributions::Uniform, Rng, SeedableRng};
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let range = Uniform::new(-1e3, 1e3);
+        for _ in 0..100_000 {
+            let target = Target {
+                position: na::Vector3::from_distribution(&range, &mut rng),
+                velocity: na::Vector3::from_distribution(&range, &mut rng),
+            };
+            let speed = rng.gen_range(0.0, 2e3);
+            let expected = reference_linear_aim(&target, speed);
+            let actual = linear_aim(&target, speed).map(<(_, _)>::from);
+            match (expected, actual) {
+                (None, None) => {}
+                (Some((d0, t0)), Some((d1, t1))) => {
+                    assert_eq!(t0.to_bits(), t1.to_bits());
+                    assert_eq!(d0, d1);
+                }
+                _ => panic!("{:?} != {:?}", expected, actual),
+            }
+        }
+    }
+
     /// Find the miss distance
-    fn miss(mut target: Target<f64>) -> f64 {
+    fn miss(target: Target<f64>) -> f64 {
+        miss_with(target, |target| {
+            let acceleration = ipn(3.0, target);
+            assert!(acceleration.dot(&target.velocity).abs() < 1e-3);
+            acceleration
+        })
+    }
+
+    /// Find the miss distance under an arbitrary guidance law
+    fn miss_with(mut target: Target<f64>, law: impl Fn(&Target<f64>) -> na::Vector3<f64>) -> f64 {
         const TIMESTEP: f64 = 1e-2;
         while target.is_closing() {
             // Semi-implicit euler integration
-            let acceleration = ipn(3.0, &target);
-            assert!(acceleration.dot(&target.velocity).abs() < 1e-3);
+            let acceleration = law(&target);
             target.velocity += TIMESTEP * -acceleration;
             target.position += TIMESTEP * target.velocity;
-            println!(
-                "x: {:?}; v: {:?}; a: {:?}",
-                target.position.data,
-                (-target.velocity).data,
-                acceleration.data
-            );
         }
-        let distance = target.position.norm();
-        println!("miss: {}", distance);
-        distance
+        target.position.norm()
     }
 }
@@ -0,0 +1,177 @@
+//! Target motion models beyond constant velocity
+
+use na::RealField;
+
+use crate::aim::earliest_root;
+use crate::{linear_aim, AimSolution, Target};
+
+/// A target flying a coordinated turn at constant speed and turn rate
+#[derive(Debug, Copy, Clone)]
+pub struct CoordinatedTurn<N: RealField> {
+    pub position: na::Vector3<N>,
+    pub velocity: na::Vector3<N>,
+    /// Angular velocity of the velocity vector, in radians per unit time; should be perpendicular
+    /// to `velocity`
+    pub turn_rate: na::Vector3<N>,
+}
+
+impl<N: RealField> CoordinatedTurn<N> {
+    /// Exact state after `dt`
+    pub fn predict(&self, dt: N) -> Self {
+        let rate = self.turn_rate.norm();
+        let axis = match na::Unit::try_new(self.turn_rate, N::default_epsilon()) {
+            Some(axis) => axis,
+            None => {
+                return Self {
+                    position: self.position + self.velocity * dt,
+                    ..*self
+                }
+            }
+        };
+        let angle = rate * dt;
+        let along = axis.as_ref() * axis.dot(&self.velocity);
+        let across = self.velocity - along;
+        let displacement = along * dt
+            + across * (angle.sin() / rate)
+            + axis.cross(&across) * ((N::one() - angle.cos()) / rate);
+        Self {
+            position: self.position + displacement,
+            velocity: na::Rotation3::from_axis_angle(&axis, angle) * self.velocity,
+            turn_rate: self.turn_rate,
+        }
+    }
+
+    /// The instantaneous constant-velocity approximation of this target
+    #[inline]
+    pub fn tangent(&self) -> Target<N> {
+        Target {
+            position: self.position,
+            velocity: self.velocity,
+        }
+    }
+}
+
+/// Direction to aim a projectile that will travel at `speed` to hit a turning target, and time of
+/// impact
+///
+/// Accounts for the target's turn where `linear_aim` would lead it along its current tangent.
+pub fn ct_aim<N: RealField>(target: &CoordinatedTurn<N>, speed: N) -> Option<AimSolution<N>> {
+    let rate = target.turn_rate.norm();
+    if rate <= N::default_epsilon() {
+        return linear_aim(&target.tangent(), speed);
+    }
+    // Range grows no faster than the target's speed, bounding the search when we're faster
+    let target_speed = target.velocity.norm();
+    let horizon = if speed > target_speed {
+        target.position.norm() / (speed - target_speed)
+    } else {
+        N::two_pi() / rate
+    };
+    let t = earliest_root(
+        |t| target.predict(t).position.norm() - speed * t,
+        na::zero(),
+        horizon,
+    )?;
+    let point = target.predict(t).position;
+    Some(AimSolution {
+        direction: na::Unit::try_new(point, na::zero())?,
+        time: t,
+        point,
+        required_speed: speed,
+    })
+}
+
+/// Sphere containing every position `target` can reach after `time` while accelerating by no more
+/// than `max_accel`
+///
+/// Returns the center, which is the straight-line prediction, and the radius
+/// `0.5 * max_accel * time²`.
+pub fn reachable_cone<N: RealField>(
+    target: &Target<N>,
+    max_accel: N,
+    time: N,
+) -> (na::Vector3<N>, N) {
+    (
+        target.position + target.velocity * time,
+        na::convert::<_, N>(0.5) * max_accel * time * time,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turning() -> CoordinatedTurn<f64> {
+        CoordinatedTurn {
+            position: na::Vector3::new(3000.0, 1000.0, 0.0),
+            velocity: na::Vector3::new(0.0, -250.0, 0.0),
+            turn_rate: na::Vector3::new(0.0, 0.0, 0.1),
+        }
+    }
+
+    #[test]
+    fn predict_exact() {
+        let target = turning();
+        // A full revolution returns to the start
+        let period = 2.0 * std::f64::consts::PI / 0.1;
+        let full = target.predict(period);
+        assert!((full.position - target.position).norm() < 1e-9);
+        assert!((full.velocity - target.velocity).norm() < 1e-9);
+        // Many small steps agree with one large one
+        let mut stepped = target;
+        for _ in 0..100 {
+            stepped = stepped.predict(0.05);
+        }
+        assert!((stepped.position - target.predict(5.0).position).norm() < 1e-9);
+        assert!((stepped.velocity.norm() - 250.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn turning_intercept() {
+        let target = turning();
+        let speed = 600.0;
+        let miss = |solution: AimSolution<f64>| {
+            let t = solution.time;
+            (solution.direction.into_inner() * speed * t - target.predict(t).position).norm()
+        };
+        let turn_aware = miss(ct_aim(&target, speed).unwrap());
+        let straight = miss(linear_aim(&target.tangent(), speed).unwrap());
+        assert!(turn_aware < 1e-6);
+        assert!(straight > 100.0);
+    }
+
+    #[test]
+    fn reachable() {
+        use rand::{distributions::Uniform, SeedableRng};
+        let target = Target {
+            position: na::Vector3::new(2000.0, -500.0, 100.0),
+            velocity: na::Vector3::new(-200.0, 50.0, 0.0),
+        };
+        let max_accel = 60.0f64;
+        let (_, r1) = reachable_cone(&target, max_accel, 1.0);
+        let (_, r3) = reachable_cone(&target, max_accel, 3.0);
+        assert!((r3 / r1 - 9.0).abs() < 1e-12);
+
+        const TIMESTEP: f64 = 1e-2;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let unit = Uniform::new(-1.0, 1.0);
+        for _ in 0..100 {
+            let mut state = target;
+            let mut accel = na::zero();
+            for step in 1..=500 {
+                // Switch to a new bounded maneuver every second
+                if step % 100 == 1 {
+                    let direction = na::Vector3::<f64>::from_distribution(&unit, &mut rng);
+                    accel = direction.normalize() * max_accel;
+                }
+                state.velocity += accel * TIMESTEP;
+                state.position += state.velocity * TIMESTEP;
+                // Semi-implicit Euler overshoots the continuous trajectory by O(dt)
+                let time = f64::from(step) * TIMESTEP;
+                let (center, radius) = reachable_cone(&target, max_accel, time);
+                let slack = max_accel * time * TIMESTEP;
+                assert!((state.position - center).norm() <= radius + slack);
+            }
+        }
+    }
+}
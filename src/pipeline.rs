@@ -0,0 +1,206 @@
+//! Declarative descriptions of guidance law pipelines
+//!
+//! A `PipelineConfig` records a base law and the wrappers applied to it, so that an experiment can
+//! be reproduced from a saved description. With the `serde` feature, configurations implement
+//! `Serialize` and `Deserialize`, with laws and wrappers tagged by their snake_case names, e.g.
+//! as JSON:
+//!
+//! ```text
+//! {
+//!   "law": { "ipn": { "navigation_constant": 3.0 } },
+//!   "stages": [
+//!     { "tracked": { "alpha": 0.5, "beta": 0.2 } },
+//!     { "acceleration_limit": { "max_accel": 400.0 } },
+//!     { "latency": { "delay": 0.02 } }
+//!   ]
+//! }
+//! ```
+
+use na::RealField;
+
+use crate::{
+    AccelerationLimit, Compensated, Filtered, GuidanceLaw, Ipn, Latency, LinearSteer, LosFilter,
+    LowPass, PurePursuit, RateLimiter, Tpn, Tracked, TransientSuppressor,
+};
+
+/// A base guidance law and the wrappers applied around it
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PipelineConfig<N: RealField> {
+    pub law: LawConfig<N>,
+    /// Wrappers, innermost first
+    pub stages: Vec<StageConfig<N>>,
+}
+
+/// Parameters of a base guidance law
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum LawConfig<N: RealField> {
+    /// See `Ipn`
+    Ipn { navigation_constant: N },
+    /// See `Tpn`
+    Tpn { navigation_constant: N },
+    /// See `PurePursuit`
+    PurePursuit,
+    /// See `LinearSteer`
+    LinearSteer { average_speed: N },
+}
+
+/// Parameters of a guidance law wrapper
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum StageConfig<N: RealField> {
+    /// See `Compensated`
+    Compensated { gravity: na::Vector3<N>, drag: N },
+    /// See `RateLimiter::new`
+    RateLimiter { rate: N },
+    /// See `TransientSuppressor::new`
+    TransientSuppressor { threshold: N, ramp_time: N },
+    /// See `AccelerationLimit`
+    AccelerationLimit { max_accel: N },
+    /// A `Filtered` command through a `LowPass` with the given cutoff
+    LowPass { cutoff: N },
+    /// A `Tracked` target estimate from a `LosFilter` with the given gains
+    Tracked { alpha: N, beta: N },
+    /// See `Latency::new`
+    Latency { delay: N },
+}
+
+impl<N: RealField> PipelineConfig<N> {
+    /// Construct the described law
+    pub fn build(&self) -> Box<dyn GuidanceLaw<N>> {
+        let mut law: Box<dyn GuidanceLaw<N>> = match self.law {
+            LawConfig::Ipn {
+                navigation_constant,
+            } => Box::new(Ipn {
+                navigation_constant,
+            }),
+            LawConfig::Tpn {
+                navigation_constant,
+            } => Box::new(Tpn {
+                navigation_constant,
+            }),
+            LawConfig::PurePursuit => Box::new(PurePursuit),
+            LawConfig::LinearSteer { average_speed } => Box::new(LinearSteer { average_speed }),
+        };
+        for stage in &self.stages {
+            law = match *stage {
+                StageConfig::Compensated { gravity, drag } => {
+                    Box::new(Compensated { law, gravity, drag })
+                }
+                StageConfig::RateLimiter { rate } => Box::new(RateLimiter::new(law, rate)),
+                StageConfig::TransientSuppressor {
+                    threshold,
+                    ramp_time,
+                } => Box::new(TransientSuppressor::new(law, threshold, ramp_time)),
+                StageConfig::AccelerationLimit { max_accel } => {
+                    Box::new(AccelerationLimit { law, max_accel })
+                }
+                StageConfig::LowPass { cutoff } => Box::new(Filtered {
+                    law,
+                    filter: LowPass::new(cutoff),
+                }),
+                StageConfig::Tracked { alpha, beta } => Box::new(Tracked {
+                    law,
+                    filter: LosFilter::new(alpha, beta),
+                }),
+                StageConfig::Latency { delay } => Box::new(Latency::new(law, delay)),
+            };
+        }
+        law
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Target;
+
+    /// Fly `a` and `b` against the same initial target, asserting they command identically
+    fn assert_equivalent(mut a: Box<dyn GuidanceLaw<f64>>, mut b: Box<dyn GuidanceLaw<f64>>) {
+        let mut target = Target {
+            position: na::Vector3::new(500.0, 3000.0, 100.0),
+            velocity: na::Vector3::new(-20.0, -600.0, 0.0),
+        };
+        let velocity = na::Vector3::new(0.0, 500.0, 0.0);
+        let mut moved = false;
+        for _ in 0..200 {
+            let command = a.command(&target, &velocity, 1e-3);
+            assert_eq!(command, b.command(&target, &velocity, 1e-3));
+            moved |= command != na::zero();
+            target.velocity -= command * 1e-3;
+            target.position += target.velocity * 1e-3;
+        }
+        assert!(moved);
+    }
+
+    #[test]
+    fn build() {
+        let config = PipelineConfig {
+            law: LawConfig::Ipn {
+                navigation_constant: 3.0,
+            },
+            stages: vec![
+                StageConfig::AccelerationLimit { max_accel: 50.0 },
+                StageConfig::Latency { delay: 0.01 },
+            ],
+        };
+        let manual = Latency::new(
+            AccelerationLimit {
+                law: Ipn {
+                    navigation_constant: 3.0,
+                },
+                max_accel: 50.0,
+            },
+            0.01,
+        );
+        assert_equivalent(config.build(), Box::new(manual));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn round_trip() {
+        let laws = [
+            LawConfig::Ipn {
+                navigation_constant: 3.5,
+            },
+            LawConfig::Tpn {
+                navigation_constant: 4.0,
+            },
+            LawConfig::PurePursuit,
+            LawConfig::LinearSteer {
+                average_speed: 500.0,
+            },
+        ];
+        let stages = vec![
+            StageConfig::Tracked {
+                alpha: 0.5,
+                beta: 0.2,
+            },
+            StageConfig::LowPass { cutoff: 30.0 },
+            StageConfig::Compensated {
+                gravity: na::Vector3::new(0.0, 0.0, -9.81),
+                drag: 1e-4,
+            },
+            StageConfig::RateLimiter { rate: 50.0 },
+            StageConfig::TransientSuppressor {
+                threshold: 100.0,
+                ramp_time: 0.2,
+            },
+            StageConfig::AccelerationLimit { max_accel: 400.0 },
+            StageConfig::Latency { delay: 0.02 },
+        ];
+        for &law in &laws {
+            let config = PipelineConfig {
+                law,
+                stages: stages.clone(),
+            };
+            let json = serde_json::to_string(&config).unwrap();
+            let parsed = serde_json::from_str::<PipelineConfig<f64>>(&json).unwrap();
+            assert_eq!(parsed, config);
+            assert_equivalent(config.build(), parsed.build());
+        }
+    }
+}
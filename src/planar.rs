@@ -0,0 +1,141 @@
+//! Planar engagements, computed without a third axis
+//!
+//! Mirrors the core API for targets confined to a plane. Where the 3D math uses cross products,
+//! angular quantities here are scalars, positive counterclockwise.
+
+use na::RealField;
+
+use crate::{intercept_times, Target, Tolerances};
+
+/// A target's state relative to the pursuer, in the plane
+#[derive(Debug, Copy, Clone)]
+pub struct Target2<N: RealField> {
+    pub position: na::Vector2<N>,
+    pub velocity: na::Vector2<N>,
+}
+
+impl<N: RealField> Target2<N> {
+    /// Whether the target is currently approaching the origin
+    #[inline]
+    pub fn is_closing(&self) -> bool {
+        self.position.dot(&self.velocity) < na::zero()
+    }
+
+    /// Relative angular momentum per unit mass about the origin, the scalar `position × velocity`
+    #[inline]
+    pub fn angular_momentum(&self) -> N {
+        self.position.perp(&self.velocity)
+    }
+}
+
+/// The same target in the z = 0 plane in three dimensions
+impl<N: RealField> From<Target2<N>> for Target<N> {
+    fn from(target: Target2<N>) -> Self {
+        let (p, v) = (target.position, target.velocity);
+        Self {
+            position: na::Vector3::new(p.x, p.y, na::zero()),
+            velocity: na::Vector3::new(v.x, v.y, na::zero()),
+        }
+    }
+}
+
+/// Angular velocity of the line of sight, counterclockwise
+pub fn los_rate2<N: RealField>(target: &Target2<N>) -> N {
+    target.angular_momentum() / target.position.norm_squared()
+}
+
+/// `ipn` in the plane
+///
+/// `target.is_closing()` must be true.
+pub fn ipn2<N: RealField>(navigation_constant: N, target: &Target2<N>) -> na::Vector2<N> {
+    debug_assert!(target.is_closing());
+    // v × w_s, with w_s normal to the plane
+    let v = target.velocity;
+    na::Vector2::new(v.y, -v.x) * (navigation_constant * los_rate2(target))
+}
+
+/// A solution to a planar aiming problem, as `AimSolution`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AimSolution2<N: RealField> {
+    /// Direction in which to launch the projectile
+    pub direction: na::Unit<na::Vector2<N>>,
+    /// Time from launch until impact
+    pub time: N,
+    /// Point of impact, relative to the launch point
+    pub point: na::Vector2<N>,
+    /// Launch speed of the projectile
+    pub required_speed: N,
+}
+
+/// `linear_aim` in the plane
+pub fn linear_aim2<N: RealField>(target: &Target2<N>, speed: N) -> Option<AimSolution2<N>> {
+    let times = intercept_times(
+        target.position.norm_squared(),
+        target.position.dot(&target.velocity),
+        target.velocity.norm_squared(),
+        speed,
+        &Tolerances::default(),
+    );
+    let t = match times {
+        Some([first, _]) => first?,
+        None => {
+            return Some(AimSolution2 {
+                direction: na::Unit::new_normalize(target.position),
+                time: na::zero(),
+                point: target.position,
+                required_speed: speed,
+            })
+        }
+    };
+    let point = target.position + target.velocity * t;
+    Some(AimSolution2 {
+        direction: na::Unit::new_normalize(point),
+        time: t,
+        point,
+        required_speed: speed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ipn, linear_aim};
+
+    fn crossing() -> Target2<f64> {
+        Target2 {
+            position: na::Vector2::new(500.0, 3000.0),
+            velocity: na::Vector2::new(-150.0, -400.0),
+        }
+    }
+
+    #[test]
+    fn planar_intercept() {
+        const TIMESTEP: f64 = 1e-3;
+        let mut target = crossing();
+        let mut closest = target.position.norm();
+        while target.is_closing() {
+            target.velocity -= ipn2(3.0, &target) * TIMESTEP;
+            target.position += target.velocity * TIMESTEP;
+            closest = closest.min(target.position.norm());
+        }
+        assert!(closest < 1.0);
+    }
+
+    #[test]
+    fn matches_3d() {
+        let planar = crossing();
+        let spatial = Target::from(planar);
+        assert_eq!(planar.angular_momentum(), spatial.angular_momentum().z);
+        let command = ipn2(3.0, &planar);
+        let expected = ipn(3.0, &spatial);
+        assert!((na::Vector3::new(command.x, command.y, 0.0) - expected).norm() < 1e-9);
+
+        let aim = linear_aim2(&planar, 300.0).unwrap();
+        let expected = linear_aim(&spatial, 300.0).unwrap();
+        assert_eq!(aim.time, expected.time);
+        assert!((aim.direction.x - expected.direction.x).abs() < 1e-12);
+        assert!((aim.direction.y - expected.direction.y).abs() < 1e-12);
+        let impact = aim.direction.into_inner() * 300.0 * aim.time;
+        assert!((impact - (planar.position + planar.velocity * aim.time)).norm() < 1e-6);
+    }
+}
@@ -0,0 +1,107 @@
+//! Open-loop acceleration profiles
+
+use na::RealField;
+
+use crate::Target;
+
+/// Minimum-jerk acceleration profile from the current state to `intercept_point` at `time_to_go`
+///
+/// Returns the commanded acceleration as a function of time elapsed since the profile began.
+/// Acceleration starts and ends at zero and the terminal velocity is left free, e.g. to connect
+/// the current state to a solution from `linear_aim`.
+pub fn min_jerk_profile<N: RealField>(
+    current_position: na::Point3<N>,
+    current_velocity: na::Vector3<N>,
+    intercept_point: na::Point3<N>,
+    time_to_go: N,
+) -> impl Fn(N) -> na::Vector3<N> {
+    // Quintic satisfying a(0) = a(T) = 0 and the free-velocity condition p''''(T) = 0
+    let miss = intercept_point - (current_position + current_velocity * time_to_go);
+    let gain = miss * (na::convert::<_, N>(7.5) / (time_to_go * time_to_go));
+    move |t| {
+        let s = na::clamp(t / time_to_go, na::zero(), na::one());
+        gain * (s * (N::one() - s) * (na::convert::<_, N>(2.) - s))
+    }
+}
+
+/// Minimum-effort acceleration profile intercepting `target` in exactly `time_to_go`
+///
+/// Returns the commanded acceleration as a function of the time remaining, minimizing the
+/// integral of squared acceleration. This is the open-loop form of the classic linear-quadratic
+/// solution `3 * ZEM / t_go^2`, decaying linearly to zero at intercept.
+pub fn min_effort_profile<N: RealField>(
+    target: &Target<N>,
+    time_to_go: N,
+) -> impl Fn(N) -> na::Vector3<N> {
+    let zem = target.position + target.velocity * time_to_go;
+    let gain = zem * (na::convert::<_, N>(3.) / (time_to_go * time_to_go * time_to_go));
+    move |remaining| gain * na::clamp(remaining, na::zero(), time_to_go)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ipn, linear_aim};
+
+    #[test]
+    fn min_jerk_reaches_intercept() {
+        const STEPS: usize = 10_000;
+        let target = Target {
+            position: na::Vector3::new(2000.0, 500.0, 0.0),
+            velocity: na::Vector3::new(-100.0, 50.0, 0.0),
+        };
+        let solution = linear_aim(&target, 600.0).unwrap();
+        let time_to_go = solution.time;
+        let intercept = na::Point3::from(solution.point);
+        let velocity = na::Vector3::new(300.0, 0.0, 100.0);
+        let profile = min_jerk_profile(na::Point3::origin(), velocity, intercept, time_to_go);
+        assert_eq!(profile(0.0), na::zero());
+
+        let dt = time_to_go / STEPS as f64;
+        let mut position = na::Point3::origin();
+        let mut velocity = velocity;
+        for i in 0..STEPS {
+            // Midpoint rule, exact for the cubic acceleration up to O(dt^2)
+            let a = profile((i as f64 + 0.5) * dt);
+            position += velocity * dt + a * (0.5 * dt * dt);
+            velocity += a * dt;
+        }
+        assert!(profile(time_to_go).norm() < 1e-9);
+        assert!(na::distance(&position, &intercept) < 1e-2);
+    }
+
+    #[test]
+    fn min_effort() {
+        const TIMESTEP: f64 = 1e-4;
+        let initial = Target {
+            position: na::Vector3::new(200.0, -100.0, 5000.0),
+            velocity: na::Vector3::new(0.0, 20.0, -500.0),
+        };
+        // Fly proportional navigation first to find a comparable duration
+        let mut target = initial;
+        let mut pn_effort = 0.0;
+        let mut time_to_go = 0.0;
+        while target.is_closing() {
+            let a = ipn(3.0, &target);
+            pn_effort += a.norm_squared() * TIMESTEP;
+            target.velocity -= a * TIMESTEP;
+            target.position += target.velocity * TIMESTEP;
+            time_to_go += TIMESTEP;
+        }
+        assert!(target.position.norm() < 1.0);
+
+        let profile = min_effort_profile(&initial, time_to_go);
+        let mut target = initial;
+        let mut effort = 0.0;
+        let steps = (time_to_go / TIMESTEP).round() as usize;
+        for i in 0..steps {
+            // Midpoint of the step
+            let a = profile(time_to_go - (i as f64 + 0.5) * TIMESTEP);
+            effort += a.norm_squared() * TIMESTEP;
+            target.position += target.velocity * TIMESTEP - a * (0.5 * TIMESTEP * TIMESTEP);
+            target.velocity -= a * TIMESTEP;
+        }
+        assert!(target.position.norm() < 1.0);
+        assert!(effort < pn_effort, "effort: {}; pn: {}", effort, pn_effort);
+    }
+}
@@ -0,0 +1,327 @@
+//! Seeker measurement and acquisition models
+
+use na::RealField;
+
+use crate::{closing_speed, linear_aim, Target};
+
+/// Rounds measured line-of-sight angles to a fixed angular resolution, as a digital seeker does
+///
+/// Bearing is measured about the z axis from the x axis, and elevation from the xy plane.
+#[derive(Debug, Copy, Clone)]
+pub struct Quantizer<N: RealField> {
+    /// Size of one angular step, in radians
+    pub resolution: N,
+}
+
+impl<N: RealField> Quantizer<N> {
+    /// Snap `direction` to the nearest representable bearing and elevation
+    pub fn quantize(&self, direction: &na::Unit<na::Vector3<N>>) -> na::Unit<na::Vector3<N>> {
+        let round = |x: N| (x / self.resolution).round() * self.resolution;
+        let bearing = round(direction.y.atan2(direction.x));
+        let elevation = round(na::clamp(direction.z, -N::one(), N::one()).asin());
+        na::Unit::new_unchecked(na::Vector3::new(
+            elevation.cos() * bearing.cos(),
+            elevation.cos() * bearing.sin(),
+            elevation.sin(),
+        ))
+    }
+
+    /// The target as guidance perceives it, with the line of sight quantized and range preserved
+    pub fn measure(&self, target: &Target<N>) -> Target<N> {
+        let range = target.position.norm();
+        let position = match na::Unit::try_new(target.position, N::default_epsilon()) {
+            Some(los) => self.quantize(&los).into_inner() * range,
+            None => target.position,
+        };
+        Target {
+            position,
+            velocity: target.velocity,
+        }
+    }
+}
+
+/// Progress of a seeker towards tracking a target
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum AcquisitionState<N: RealField> {
+    /// No target is detectable
+    Searching,
+    /// A target has been detectable continuously for `dwell`
+    Acquiring { dwell: N },
+    /// The target is being tracked, and guidance may use it
+    Locked,
+}
+
+/// State machine for a seeker acquiring and holding lock on a target
+///
+/// A target is detectable within `max_range` and `field_of_view` of the boresight. Lock is
+/// declared once it has remained detectable for `dwell_time`, and lost as soon as it is not.
+#[derive(Debug, Copy, Clone)]
+pub struct Acquisition<N: RealField> {
+    pub max_range: N,
+    /// Half-angle of the field of view, in radians
+    pub field_of_view: N,
+    pub dwell_time: N,
+    state: AcquisitionState<N>,
+}
+
+impl<N: RealField> Acquisition<N> {
+    pub fn new(max_range: N, field_of_view: N, dwell_time: N) -> Self {
+        Self {
+            max_range,
+            field_of_view,
+            dwell_time,
+            state: AcquisitionState::Searching,
+        }
+    }
+
+    #[inline]
+    pub fn state(&self) -> AcquisitionState<N> {
+        self.state
+    }
+
+    /// Whether `target` can currently be seen along `boresight`
+    pub fn detectable(&self, target: &Target<N>, boresight: &na::Unit<na::Vector3<N>>) -> bool {
+        target.position.norm() <= self.max_range
+            && boresight.angle(&target.position) <= self.field_of_view
+    }
+
+    /// Advance by `dt` with the current view of `target`
+    pub fn update(
+        &mut self,
+        target: &Target<N>,
+        boresight: &na::Unit<na::Vector3<N>>,
+        dt: N,
+    ) -> AcquisitionState<N> {
+        self.state = if !self.detectable(target, boresight) {
+            AcquisitionState::Searching
+        } else {
+            match self.state {
+                AcquisitionState::Locked => AcquisitionState::Locked,
+                AcquisitionState::Searching => AcquisitionState::Acquiring { dwell: na::zero() },
+                AcquisitionState::Acquiring { dwell } if dwell + dt >= self.dwell_time => {
+                    AcquisitionState::Locked
+                }
+                AcquisitionState::Acquiring { dwell } => {
+                    AcquisitionState::Acquiring { dwell: dwell + dt }
+                }
+            }
+        };
+        self.state
+    }
+}
+
+/// Angle between `body_axis` and the line of sight to `target`, in radians
+///
+/// This is the look angle a gimballed seeker must sustain; once it exceeds the gimbal limit, lock
+/// is lost.
+pub fn boresight_error<N: RealField>(
+    target: &Target<N>,
+    body_axis: &na::Unit<na::Vector3<N>>,
+) -> N {
+    body_axis.angle(&target.position)
+}
+
+/// Line-of-sight angular velocity from the bearing and elevation rates reported by a seeker
+///
+/// Bearing is measured about `up` and elevation from the plane perpendicular to it. Only the
+/// component perpendicular to `boresight` is returned, matching the line-of-sight rate computed
+/// from a `Target`. When `boresight` is within `N::default_epsilon()` of `up` or its negation,
+/// bearing and elevation are degenerate and zero is returned.
+pub fn los_rate_from_angular_rates<N: RealField>(
+    boresight: &na::Unit<na::Vector3<N>>,
+    up: &na::Unit<na::Vector3<N>>,
+    az_rate: N,
+    el_rate: N,
+) -> na::Vector3<N> {
+    let elevation_axis = match na::Unit::try_new(boresight.cross(up), N::default_epsilon()) {
+        Some(axis) => axis,
+        None => return na::zero(),
+    };
+    let rate = up.as_ref() * az_rate + elevation_axis.as_ref() * el_rate;
+    rate - boresight.as_ref() * boresight.dot(&rate)
+}
+
+/// Engagement geometry at an instant, e.g. seeker lock, for logging and later analysis
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EngagementSnapshot<N: RealField> {
+    pub range: N,
+    /// See `closing_speed`
+    pub closing_speed: N,
+    /// Angle between the target's inertial velocity and the line of sight, in radians: zero when
+    /// it flies directly away, and π when it flies directly at the pursuer
+    pub aspect: N,
+    /// Angle between the line of sight and the collision course from `linear_aim`, in radians, or
+    /// `None` if there's no intercept
+    pub lead_angle: Option<N>,
+    /// `boresight_error` for a body axis along the pursuer's velocity, or `None` if the pursuer is
+    /// at rest, leaving no such axis
+    pub boresight_error: Option<N>,
+}
+
+impl<N: RealField> EngagementSnapshot<N> {
+    /// Record the geometry of `target` relative to a pursuer moving at `current_velocity`, whose
+    /// collision course is computed at `speed`
+    pub fn capture(target: &Target<N>, current_velocity: &na::Vector3<N>, speed: N) -> Self {
+        let inertial = Target {
+            position: target.position,
+            velocity: target.velocity + current_velocity,
+        };
+        Self {
+            range: target.position.norm(),
+            closing_speed: closing_speed(target),
+            aspect: inertial.velocity.angle(&target.position),
+            lead_angle: linear_aim(&inertial, speed)
+                .map(|solution| solution.direction.angle(&target.position)),
+            boresight_error: na::Unit::try_new(*current_velocity, N::default_epsilon())
+                .map(|axis| boresight_error(target, &axis)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipn;
+
+    fn quantized_miss(quantizer: &Quantizer<f64>) -> f64 {
+        const TIMESTEP: f64 = 1e-3;
+        let mut target = Target {
+            position: na::Vector3::new(300.0, 2000.0, 150.0),
+            velocity: na::Vector3::new(-100.0, -400.0, 0.0),
+        };
+        let mut miss = target.position.norm();
+        while target.is_closing() {
+            let acceleration = ipn(3.0, &quantizer.measure(&target));
+            target.velocity += TIMESTEP * -acceleration;
+            target.position += TIMESTEP * target.velocity;
+            miss = miss.min(target.position.norm());
+        }
+        miss
+    }
+
+    #[test]
+    fn quantize_rounds() {
+        let quantizer = Quantizer { resolution: 0.1f64 };
+        let los = na::Unit::new_normalize(na::Vector3::new(1.0, 0.26, 0.04));
+        let q = quantizer.quantize(&los);
+        assert!((q.y.atan2(q.x) - 0.3).abs() < 1e-12);
+        assert!(q.z.asin().abs() < 1e-12);
+    }
+
+    #[test]
+    fn finer_quantization() {
+        let coarse = quantized_miss(&Quantizer { resolution: 5e-2 });
+        let fine = quantized_miss(&Quantizer { resolution: 1e-4 });
+        assert!(fine < coarse, "coarse: {}; fine: {}", coarse, fine);
+    }
+
+    #[test]
+    fn angular_rates_round_trip() {
+        let up = na::Vector3::<f64>::y_axis();
+        let boresight = na::Unit::new_normalize(na::Vector3::new(3.0, 1.0, -2.0));
+        let los_rate = na::Vector3::new(0.02, -0.01, 0.05);
+        let los_rate = los_rate - boresight.as_ref() * boresight.dot(&los_rate);
+
+        // Decompose into the rates a gimballed seeker would report
+        let motion = los_rate.cross(&boresight);
+        let horizontal = boresight.as_ref() - up.as_ref() * boresight.dot(&up);
+        let horizontal_motion = motion - up.as_ref() * motion.dot(&up);
+        let az_rate = horizontal.cross(&horizontal_motion).dot(&up) / horizontal.norm_squared();
+        let el_rate = motion.dot(&up) / horizontal.norm();
+
+        let recovered = los_rate_from_angular_rates(&boresight, &up, az_rate, el_rate);
+        assert!((recovered - los_rate).norm() < 1e-12);
+        assert_eq!(los_rate_from_angular_rates(&up, &up, 1.0, 1.0), na::zero());
+    }
+
+    #[test]
+    fn acquisition() {
+        let mut seeker = Acquisition::new(1000.0, 0.2, 0.3);
+        let boresight = na::Vector3::z_axis();
+        let far = Target {
+            position: na::Vector3::new(0.0, 0.0, 1500.0),
+            velocity: na::zero(),
+        };
+        let near = Target {
+            position: na::Vector3::new(0.0, 0.0, 900.0),
+            velocity: na::zero(),
+        };
+        let wide = Target {
+            position: na::Vector3::new(900.0, 0.0, 900.0),
+            velocity: na::zero(),
+        };
+        assert_eq!(
+            seeker.update(&far, &boresight, 0.1),
+            AcquisitionState::Searching
+        );
+        assert_eq!(
+            seeker.update(&wide, &boresight, 0.1),
+            AcquisitionState::Searching
+        );
+        for _ in 0..3 {
+            assert!(matches!(
+                seeker.update(&near, &boresight, 0.1),
+                AcquisitionState::Acquiring { .. }
+            ));
+        }
+        assert_eq!(
+            seeker.update(&near, &boresight, 0.1),
+            AcquisitionState::Locked
+        );
+        assert_eq!(
+            seeker.update(&near, &boresight, 0.1),
+            AcquisitionState::Locked
+        );
+        assert_eq!(
+            seeker.update(&wide, &boresight, 0.1),
+            AcquisitionState::Searching
+        );
+    }
+
+    #[test]
+    fn boresight() {
+        let axis = na::Vector3::<f64>::y_axis();
+        let ahead = Target {
+            position: na::Vector3::new(0.0, 5000.0, 0.0),
+            velocity: na::Vector3::new(0.0, -300.0, 0.0),
+        };
+        let beam = Target {
+            position: na::Vector3::new(0.0, 0.0, 2000.0),
+            velocity: na::Vector3::new(0.0, -300.0, 0.0),
+        };
+        assert!(boresight_error(&ahead, &axis).abs() < 1e-12);
+        assert!((boresight_error(&beam, &axis) - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn snapshot() {
+        let target = Target::<f64> {
+            position: na::Vector3::new(800.0, 5000.0, 300.0),
+            velocity: na::Vector3::new(-200.0, -900.0, 0.0),
+        };
+        let own_velocity = na::Vector3::new(0.0, 600.0, 50.0);
+        let snapshot = EngagementSnapshot::capture(&target, &own_velocity, 600.0);
+        assert_eq!(snapshot.range, target.position.norm());
+        assert_eq!(snapshot.closing_speed, closing_speed(&target));
+        let heading = target.velocity + own_velocity;
+        assert_eq!(snapshot.aspect, heading.angle(&target.position));
+        let inertial = Target {
+            velocity: heading,
+            ..target
+        };
+        let lead = linear_aim(&inertial, 600.0).unwrap().direction;
+        assert_eq!(snapshot.lead_angle, Some(lead.angle(&target.position)));
+        let axis = na::Unit::new_normalize(own_velocity);
+        assert_eq!(
+            snapshot.boresight_error,
+            Some(boresight_error(&target, &axis))
+        );
+        // Inbound
+        assert!(snapshot.aspect > std::f64::consts::FRAC_PI_2);
+
+        // Before launch
+        let at_rest = EngagementSnapshot::capture(&target, &na::zero(), 600.0);
+        assert_eq!(at_rest.boresight_error, None);
+        assert!(at_rest.aspect.is_finite() && at_rest.lead_angle.unwrap().is_finite());
+    }
+}
@@ -0,0 +1,656 @@
+//! Engagement simulation
+//!
+//! `step_engagement` operates on fixed-size values without heap allocation, so it is suitable for
+//! embedded flight code as well as offline studies. `simulate` drives it to completion, optionally
+//! recording telemetry.
+
+use na::RealField;
+
+use crate::{clamp_acceleration, GuidanceLaw, Target};
+
+/// Inertial state of a point mass
+#[derive(Debug, Copy, Clone)]
+pub struct Body<N: RealField> {
+    pub position: na::Point3<N>,
+    pub velocity: na::Vector3<N>,
+}
+
+impl<N: RealField> Body<N> {
+    /// Advance by `dt` under constant `acceleration` with semi-implicit Euler integration
+    #[inline]
+    pub fn integrate(&mut self, acceleration: &na::Vector3<N>, dt: N) {
+        self.velocity += acceleration * dt;
+        self.position += self.velocity * dt;
+    }
+}
+
+/// State of a missile guiding towards a target with constant acceleration
+#[derive(Debug, Copy, Clone)]
+pub struct EngagementState<N: RealField> {
+    pub missile: Body<N>,
+    pub target: Body<N>,
+    /// Acceleration of the target; zero unless set after construction
+    pub target_accel: na::Vector3<N>,
+    /// Largest acceleration the missile can achieve; commands are clamped to this magnitude
+    pub max_accel: N,
+    /// Time elapsed since the start of the engagement
+    pub time: N,
+    /// Largest acceleration magnitude applied so far
+    pub peak_accel: N,
+    /// Acceleration applied during the most recent step
+    pub command: na::Vector3<N>,
+}
+
+impl<N: RealField> EngagementState<N> {
+    pub fn new(missile: Body<N>, target: Body<N>, max_accel: N) -> Self {
+        Self {
+            missile,
+            target,
+            target_accel: na::zero(),
+            max_accel,
+            time: na::zero(),
+            peak_accel: na::zero(),
+            command: na::zero(),
+        }
+    }
+
+    /// The target relative to the missile, as guidance laws expect
+    #[inline]
+    pub fn relative(&self) -> Target<N> {
+        Target::relative(
+            &self.missile.position,
+            &self.missile.velocity,
+            &self.target.position,
+            &self.target.velocity,
+        )
+    }
+
+    /// The missile relative to the target, i.e. `relative()` negated
+    ///
+    /// Expressing several interceptors in the frame of their common target simplifies
+    /// coordination. A guidance law evaluated on this state computes the acceleration the *target*
+    /// would need to steer onto the missile; since the relative state is negated, the missile's
+    /// inertial command is that acceleration negated.
+    #[inline]
+    pub fn in_target_frame(&self) -> Target<N> {
+        Target {
+            position: self.missile.position - self.target.position,
+            velocity: self.missile.velocity - self.target.velocity,
+        }
+    }
+
+    /// Current distance between missile and target
+    #[inline]
+    pub fn range(&self) -> N {
+        na::distance(&self.missile.position, &self.target.position)
+    }
+}
+
+/// Result of a single `step_engagement`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StepOutcome {
+    /// The missile is still closing on the target
+    Closing,
+    /// Closest approach has passed, so `EngagementState::range` is approximately the miss distance
+    Ended,
+}
+
+/// Advance `state` by `dt`, steering the missile with `law`
+pub fn step_engagement<N: RealField, L: GuidanceLaw<N>>(
+    state: &mut EngagementState<N>,
+    law: &mut L,
+    dt: N,
+) -> StepOutcome {
+    step_engagement_substeps(state, law, dt, 1)
+}
+
+/// `step_engagement`, integrating in `substeps` equal parts while holding the command constant
+///
+/// Improves accuracy when a coarse `dt` would let high accelerations overshoot, without changing
+/// the rate at which `law` is evaluated. Integration stops early if the engagement ends partway
+/// through.
+pub fn step_engagement_substeps<N: RealField, L: GuidanceLaw<N>>(
+    state: &mut EngagementState<N>,
+    law: &mut L,
+    dt: N,
+    substeps: u32,
+) -> StepOutcome {
+    let observed = state.relative();
+    step_observed(state, law, &observed, dt, substeps)
+}
+
+/// `step_engagement_substeps` with `law` seeing the target at `observed`, rather than its true
+/// state
+fn step_observed<N: RealField, L: GuidanceLaw<N>>(
+    state: &mut EngagementState<N>,
+    law: &mut L,
+    observed: &Target<N>,
+    dt: N,
+    substeps: u32,
+) -> StepOutcome {
+    let command = clamp_acceleration(
+        law.command(observed, &state.missile.velocity, dt),
+        state.max_accel,
+    );
+    state.peak_accel = state.peak_accel.max(command.norm());
+    state.command = command;
+    let substeps = substeps.max(1);
+    let h = dt / na::convert::<_, N>(f64::from(substeps));
+    for _ in 0..substeps {
+        state.target.integrate(&state.target_accel, h);
+        state.missile.integrate(&command, h);
+        state.time += h;
+        if !state.relative().is_closing() {
+            return StepOutcome::Ended;
+        }
+    }
+    StepOutcome::Closing
+}
+
+/// An engagement advanced one guidance update at a time
+///
+/// Wraps `step_engagement_substeps` for callers that want to observe or intervene between steps,
+/// e.g. to render or to sweep over many randomized engagements, while tracking the miss distance.
+#[derive(Debug, Clone)]
+pub struct Simulation<N: RealField, L> {
+    pub state: EngagementState<N>,
+    pub law: L,
+    /// Interval between guidance updates
+    pub timestep: N,
+    /// Integration steps per `timestep`
+    pub substeps: u32,
+    miss: N,
+    ended: bool,
+}
+
+impl<N: RealField, L: GuidanceLaw<N>> Simulation<N, L> {
+    pub fn new(state: EngagementState<N>, law: L, timestep: N) -> Self {
+        Self {
+            miss: state.range(),
+            state,
+            law,
+            timestep,
+            substeps: 1,
+            ended: false,
+        }
+    }
+
+    /// Advance by one `timestep`, returning whether the engagement has ended
+    ///
+    /// Once ended, further calls have no effect.
+    pub fn step(&mut self) -> bool {
+        if !self.ended {
+            let outcome = step_engagement_substeps(
+                &mut self.state,
+                &mut self.law,
+                self.timestep,
+                self.substeps,
+            );
+            self.miss = self.miss.min(self.state.range());
+            self.ended = outcome == StepOutcome::Ended;
+        }
+        self.ended
+    }
+
+    /// Smallest range reached so far; the miss distance once `step` returns true
+    #[inline]
+    pub fn miss(&self) -> N {
+        self.miss
+    }
+
+    /// Largest acceleration magnitude commanded so far
+    #[inline]
+    pub fn peak_accel(&self) -> N {
+        self.state.peak_accel
+    }
+}
+
+/// Acceleration demanded over an engagement, from `acceleration_profile`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AccelStats<N: RealField> {
+    /// Largest acceleration magnitude applied
+    pub peak: N,
+    /// Integral of the applied acceleration's magnitude
+    pub delta_v: N,
+    /// Time at which the engagement ended, if it did within the allotted steps
+    pub time_to_intercept: Option<N>,
+}
+
+/// Fly `initial` under `law` for up to `max_steps` of `timestep`, measuring the acceleration
+/// demanded of the missile
+///
+/// Useful for sizing actuators, where miss distance alone says little.
+pub fn acceleration_profile<N: RealField, L: GuidanceLaw<N>>(
+    initial: EngagementState<N>,
+    law: &mut L,
+    timestep: N,
+    max_steps: usize,
+) -> AccelStats<N> {
+    let mut sim = Simulation::new(initial, law, timestep);
+    let mut delta_v = N::zero();
+    let mut time_to_intercept = None;
+    for _ in 0..max_steps {
+        let ended = sim.step();
+        delta_v += sim.state.command.norm() * timestep;
+        if ended {
+            time_to_intercept = Some(sim.state.time);
+            break;
+        }
+    }
+    AccelStats {
+        peak: sim.peak_accel(),
+        delta_v,
+        time_to_intercept,
+    }
+}
+
+/// Parameters for `simulate`
+#[derive(Debug, Copy, Clone)]
+pub struct SimConfig<N: RealField> {
+    /// Interval between guidance updates
+    pub timestep: N,
+    /// Integration steps per `timestep`, as in `step_engagement_substeps`
+    pub substeps: u32,
+    /// Time after which to give up if the engagement hasn't ended
+    pub max_time: N,
+    /// Whether to record `Telemetry`
+    pub telemetry: bool,
+}
+
+/// Per-step time series recorded by `simulate`, as parallel channels
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct Telemetry<N: RealField> {
+    pub time: Vec<N>,
+    pub range: Vec<N>,
+    /// Magnitude of the line-of-sight rate
+    pub los_rate: Vec<N>,
+    /// Acceleration applied to the missile
+    pub command: Vec<na::Vector3<N>>,
+    /// Missile speed
+    pub speed: Vec<N>,
+}
+
+#[cfg(feature = "std")]
+impl<N: RealField> Default for Telemetry<N> {
+    fn default() -> Self {
+        Self {
+            time: Vec::new(),
+            range: Vec::new(),
+            los_rate: Vec::new(),
+            command: Vec::new(),
+            speed: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N: RealField> Telemetry<N> {
+    fn record(&mut self, state: &EngagementState<N>) {
+        self.time.push(state.time);
+        self.range.push(state.range());
+        self.los_rate
+            .push(crate::los_rate(&state.relative()).norm());
+        self.command.push(state.command);
+        self.speed.push(state.missile.velocity.norm());
+    }
+}
+
+/// Results of `simulate`
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct SimResult<N: RealField> {
+    /// State at the end of the simulation
+    pub state: EngagementState<N>,
+    /// Smallest range reached
+    pub miss: N,
+    /// Recorded if requested by `SimConfig::telemetry`
+    pub telemetry: Option<Telemetry<N>>,
+}
+
+/// Run an engagement until it ends or `config.max_time` elapses
+#[cfg(feature = "std")]
+pub fn simulate<N: RealField, L: GuidanceLaw<N>>(
+    state: EngagementState<N>,
+    law: &mut L,
+    config: &SimConfig<N>,
+) -> SimResult<N> {
+    simulate_with_dropout(state, law, config, &Dropout::default())
+}
+
+/// Intervals of simulation time during which the seeker provides no measurements
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct Dropout<N: RealField> {
+    /// `(start, end)` times of each dropout
+    pub intervals: Vec<(N, N)>,
+}
+
+#[cfg(feature = "std")]
+impl<N: RealField> Default for Dropout<N> {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N: RealField> Dropout<N> {
+    /// Whether measurements are unavailable at `time`
+    pub fn active(&self, time: N) -> bool {
+        self.intervals
+            .iter()
+            .any(|&(start, end)| time >= start && time < end)
+    }
+}
+
+/// `simulate`, with guidance coasting through seeker `dropout`
+///
+/// While measurements are unavailable, the target's last measured inertial state is dead-reckoned
+/// with `Target::predict`, and guidance operates on that estimate. True measurements resume as
+/// soon as the dropout ends.
+#[cfg(feature = "std")]
+pub fn simulate_with_dropout<N: RealField, L: GuidanceLaw<N>>(
+    mut state: EngagementState<N>,
+    law: &mut L,
+    config: &SimConfig<N>,
+    dropout: &Dropout<N>,
+) -> SimResult<N> {
+    let mut telemetry = if config.telemetry {
+        Some(Telemetry::default())
+    } else {
+        None
+    };
+    let mut miss = state.range();
+    if let Some(ref mut telemetry) = telemetry {
+        telemetry.record(&state);
+    }
+    // Inertial target state as last measured
+    let mut estimate = Target {
+        position: state.target.position.coords,
+        velocity: state.target.velocity,
+    };
+    while state.time < config.max_time {
+        estimate = if dropout.active(state.time) {
+            estimate.predict(config.timestep)
+        } else {
+            Target {
+                position: state.target.position.coords,
+                velocity: state.target.velocity,
+            }
+        };
+        let observed = Target {
+            position: estimate.position - state.missile.position.coords,
+            velocity: estimate.velocity - state.missile.velocity,
+        };
+        let outcome = step_observed(&mut state, law, &observed, config.timestep, config.substeps);
+        miss = miss.min(state.range());
+        if let Some(ref mut telemetry) = telemetry {
+            telemetry.record(&state);
+        }
+        if outcome == StepOutcome::Ended {
+            break;
+        }
+    }
+    SimResult {
+        state,
+        miss,
+        telemetry,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ipn;
+
+    #[test]
+    fn head_on() {
+        let mut state = EngagementState::new(
+            Body {
+                position: na::Point3::origin(),
+                velocity: na::Vector3::new(0.0, 800.0, 50.0),
+            },
+            Body {
+                position: na::Point3::new(500.0, 8000.0, 0.0),
+                velocity: na::Vector3::new(0.0, -300.0, 0.0),
+            },
+            300.0,
+        );
+        let mut law = Ipn {
+            navigation_constant: 4.0,
+        };
+        while step_engagement(&mut state, &mut law, 1e-3) == StepOutcome::Closing {}
+        assert!(state.range() < 1.0);
+        assert!(state.peak_accel > 0.0 && state.peak_accel <= 300.0);
+    }
+
+    #[test]
+    fn target_frame() {
+        let initial = EngagementState::new(
+            Body {
+                position: na::Point3::origin(),
+                velocity: na::Vector3::new(0.0, 800.0, 50.0),
+            },
+            Body {
+                position: na::Point3::new(500.0, 8000.0, 0.0),
+                velocity: na::Vector3::new(-100.0, -300.0, 0.0),
+            },
+            300.0,
+        );
+        let frame = initial.in_target_frame();
+        assert_eq!(frame.position, na::Vector3::new(-500.0, -8000.0, 0.0));
+        assert_eq!(frame.velocity, na::Vector3::new(100.0, 1100.0, 50.0));
+
+        // A weaving target, so the engagement has no symmetry to hide a wrong sign
+        let weave = |time: f64| na::Vector3::new(40.0 * (2.0 * time).sin(), 0.0, -15.0);
+        let mut missile_frame = initial;
+        let mut target_frame = initial;
+        let mut time = 0.0;
+        while missile_frame.relative().is_closing() {
+            let a = crate::ipn(3.0, &missile_frame.relative());
+            let b = -crate::ipn(3.0, &target_frame.in_target_frame());
+            for (state, command) in &mut [(&mut missile_frame, a), (&mut target_frame, b)] {
+                state.missile.integrate(command, 1e-3);
+                state.target.integrate(&weave(time), 1e-3);
+            }
+            time += 1e-3;
+        }
+        assert!(missile_frame.range() < 1.0);
+        assert!(
+            na::distance(
+                &missile_frame.missile.position,
+                &target_frame.missile.position
+            ) < 1e-9
+        );
+    }
+
+    #[test]
+    fn telemetry() {
+        let state = EngagementState::new(
+            Body {
+                position: na::Point3::origin(),
+                velocity: na::Vector3::new(0.0, 700.0, 0.0),
+            },
+            Body {
+                position: na::Point3::new(300.0, 6000.0, -100.0),
+                velocity: na::Vector3::new(0.0, -300.0, 0.0),
+            },
+            200.0,
+        );
+        let config = SimConfig {
+            timestep: 1e-3,
+            substeps: 1,
+            max_time: 30.0,
+            telemetry: true,
+        };
+        let mut law = Ipn {
+            navigation_constant: 3.0,
+        };
+        let result = simulate(state, &mut law, &config);
+        assert!(result.miss < 1.0);
+        let miss = result.miss;
+        let telemetry = result.telemetry.unwrap();
+        let samples = telemetry.time.len();
+        assert!(samples > 1);
+        for channel in &[&telemetry.range, &telemetry.los_rate, &telemetry.speed] {
+            assert_eq!(channel.len(), samples);
+        }
+        assert_eq!(telemetry.command.len(), samples);
+        let closest = telemetry.range.iter().position(|&r| r == miss).unwrap();
+        assert!(telemetry.range[..=closest].windows(2).all(|w| w[1] < w[0]));
+
+        let quiet = SimConfig {
+            telemetry: false,
+            ..config
+        };
+        assert!(simulate(state, &mut law, &quiet).telemetry.is_none());
+    }
+
+    #[test]
+    fn dropout() {
+        let mut state = EngagementState::new(
+            Body {
+                position: na::Point3::origin(),
+                velocity: na::Vector3::new(0.0, 800.0, 0.0),
+            },
+            Body {
+                position: na::Point3::new(300.0, 6000.0, 0.0),
+                velocity: na::Vector3::new(0.0, -300.0, 0.0),
+            },
+            400.0,
+        );
+        state.target_accel = na::Vector3::new(40.0, 0.0, 20.0);
+        let config = SimConfig {
+            timestep: 1e-3,
+            substeps: 1,
+            max_time: 30.0,
+            telemetry: false,
+        };
+        let mut law = Ipn {
+            navigation_constant: 4.0,
+        };
+        let mut miss = |intervals: Vec<(f64, f64)>| {
+            simulate_with_dropout(state, &mut law, &config, &Dropout { intervals }).miss
+        };
+        assert!(miss(Vec::new()) < 1.0);
+        // Brief gaps are bridged by dead reckoning
+        assert!(miss(vec![(2.0, 2.1), (3.0, 3.1), (4.0, 4.1)]) < 1.0);
+        // Coasting through the endgame lets the maneuver carry the target away
+        assert!(miss(vec![(3.5, 30.0)]) > 10.0);
+        // Guidance recovers once measurements resume in time
+        assert!(miss(vec![(1.0, 3.0)]) < 1.0);
+    }
+
+    /// Commands a fixed acceleration
+    struct Constant(na::Vector3<f64>);
+
+    impl GuidanceLaw<f64> for Constant {
+        fn command(&mut self, _: &Target<f64>, _: &na::Vector3<f64>, _: f64) -> na::Vector3<f64> {
+            self.0
+        }
+    }
+
+    #[test]
+    fn substeps() {
+        // Exactly, the missile's lateral drift of a t^2 / 2 carries it through the target at t = 10
+        let state = EngagementState::new(
+            Body {
+                position: na::Point3::origin(),
+                velocity: na::Vector3::new(0.0, 1000.0, 0.0),
+            },
+            Body {
+                position: na::Point3::new(500.0, 10_000.0, 0.0),
+                velocity: na::zero(),
+            },
+            100.0,
+        );
+        let miss = |substeps| {
+            let config = SimConfig {
+                timestep: 0.1,
+                substeps,
+                max_time: 30.0,
+                telemetry: false,
+            };
+            let mut law = Constant(na::Vector3::new(10.0, 0.0, 0.0));
+            simulate(state, &mut law, &config).miss
+        };
+        // Semi-implicit Euler overshoots by a * dt * t / 2, shrinking with the integration step
+        let misses = [miss(1), miss(2), miss(4), miss(8)];
+        assert!((misses[0] - 5.0).abs() < 1e-3);
+        assert!(misses.windows(2).all(|w| w[1] < w[0]));
+        assert!((misses[3] - 5.0 / 8.0).abs() < 1e-3);
+    }
+
+    /// Steers onto the linear intercept within a single step, as in the `anim` example
+    struct Steer;
+
+    impl GuidanceLaw<f64> for Steer {
+        fn command(
+            &mut self,
+            target: &Target<f64>,
+            v: &na::Vector3<f64>,
+            dt: f64,
+        ) -> na::Vector3<f64> {
+            if !target.is_closing() {
+                return na::zero();
+            }
+            crate::linear_steer(target, v, v.norm()).map_or_else(na::zero, |(dv, _)| dv / dt)
+        }
+    }
+
+    #[test]
+    fn stepwise() {
+        let state = EngagementState::new(
+            Body {
+                position: na::Point3::origin(),
+                velocity: na::Vector3::new(0.0, 1e3, 0.0),
+            },
+            Body {
+                position: na::Point3::new(1e4, 3e3, 0.0),
+                velocity: na::Vector3::new(-2e3, 0.0, 0.0),
+            },
+            1e3,
+        );
+        let mut sim = Simulation::new(state, Steer, 1.0 / 300.0);
+        let mut steps = 0;
+        while !sim.step() {
+            steps += 1;
+            assert!(steps < 10_000);
+        }
+        assert!(sim.miss() < 5.0);
+        // Clamped, up to rounding
+        assert!(sim.peak_accel() > 0.0 && sim.peak_accel() <= 1e3 + 1e-9);
+        let (time, miss) = (sim.state.time, sim.miss());
+        assert!(sim.step());
+        assert_eq!((sim.state.time, sim.miss()), (time, miss));
+    }
+
+    #[test]
+    fn demand() {
+        // The scene from the `anim` example
+        let state = EngagementState::new(
+            Body {
+                position: na::Point3::origin(),
+                velocity: na::Vector3::new(0.0, 1e3, 0.0),
+            },
+            Body {
+                position: na::Point3::new(1e4, 3e3, 0.0),
+                velocity: na::Vector3::new(-2e3, 0.0, 0.0),
+            },
+            1e3,
+        );
+        let mut law = Ipn {
+            navigation_constant: 3.0,
+        };
+        let stats = acceleration_profile(state, &mut law, 1e-3, 100_000);
+        let time = stats.time_to_intercept.unwrap();
+        assert!(time > 1.0 && time < 10.0);
+        // Hundreds of m/s², within the airframe's limit
+        assert!(stats.peak > 100.0 && stats.peak < 1e3);
+        assert!(stats.delta_v <= stats.peak * time);
+        let cut_short = acceleration_profile(state, &mut law, 1e-3, 10);
+        assert_eq!(cut_short.time_to_intercept, None);
+    }
+}
@@ -0,0 +1,130 @@
+//! Prioritizing between multiple targets
+
+use core::cmp::Ordering;
+
+use na::RealField;
+
+use crate::{closing_speed, closing_transition_time, linear_aim, AimSolution, Target};
+
+/// Orders targets by threat priority, greatest first: soonest time-to-go, then fastest closing
+///
+/// Targets which are not closing compare least. Suitable for use in a
+/// `std::collections::BinaryHeap`, which yields the most urgent threat first.
+#[derive(Debug, Copy, Clone)]
+pub struct ThreatKey<N: RealField> {
+    pub target: Target<N>,
+    time_to_go: Option<N>,
+    closing_speed: N,
+}
+
+impl<N: RealField> ThreatKey<N> {
+    pub fn new(target: Target<N>) -> Self {
+        Self {
+            time_to_go: closing_transition_time(&target),
+            closing_speed: closing_speed(&target),
+            target,
+        }
+    }
+}
+
+impl<N: RealField> Ord for ThreatKey<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let by_speed = || {
+            self.closing_speed
+                .partial_cmp(&other.closing_speed)
+                .unwrap_or(Ordering::Equal)
+        };
+        match (self.time_to_go, other.time_to_go) {
+            (None, None) => by_speed(),
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(x), Some(y)) => y
+                .partial_cmp(&x)
+                .unwrap_or(Ordering::Equal)
+                .then_with(by_speed),
+        }
+    }
+}
+
+impl<N: RealField> PartialOrd for ThreatKey<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N: RealField> PartialEq for ThreatKey<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N: RealField> Eq for ThreatKey<N> {}
+
+/// The closing target among `targets` which a projectile at `speed` can intercept soonest, and its
+/// `linear_aim` solution
+///
+/// Returns `None` if no closing target can be intercepted.
+pub fn best_target<N: RealField>(
+    targets: &[Target<N>],
+    speed: N,
+) -> Option<(&Target<N>, AimSolution<N>)> {
+    targets
+        .iter()
+        .filter(|target| target.is_closing())
+        .filter_map(|target| Some((target, linear_aim(target, speed)?)))
+        .min_by(|(_, a), (_, b)| a.time.partial_cmp(&b.time).unwrap_or(Ordering::Equal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BinaryHeap;
+
+    #[test]
+    fn priority() {
+        let target = |z: f64, vz: f64| Target {
+            position: na::Vector3::new(0.0, 0.0, z),
+            velocity: na::Vector3::new(0.0, 0.0, vz),
+        };
+        let receding = target(1000.0, 100.0);
+        let distant = target(10_000.0, -100.0);
+        let near = target(1000.0, -100.0);
+        // Same time-to-go as `near`, but closing faster
+        let fast = target(2000.0, -200.0);
+        let mut heap = [receding, distant, near, fast]
+            .iter()
+            .cloned()
+            .map(ThreatKey::new)
+            .collect::<BinaryHeap<_>>();
+        let order = core::iter::from_fn(|| heap.pop())
+            .map(|key| key.target.position.z)
+            .collect::<Vec<_>>();
+        assert_eq!(order, [2000.0, 1000.0, 10_000.0, 1000.0]);
+    }
+
+    #[test]
+    fn soonest_intercept() {
+        let targets = [
+            // Near, but receding faster than the projectile can follow
+            Target {
+                position: na::Vector3::new(0.0, 500.0, 0.0),
+                velocity: na::Vector3::new(0.0, 900.0, 0.0),
+            },
+            // Distant but closing fast
+            Target {
+                position: na::Vector3::new(0.0, 0.0, 6000.0),
+                velocity: na::Vector3::new(0.0, 0.0, -900.0),
+            },
+            // Nearer, but crossing slowly
+            Target {
+                position: na::Vector3::new(3000.0, 2000.0, 0.0),
+                velocity: na::Vector3::new(-10.0, 0.0, -50.0),
+            },
+        ];
+        let (best, solution) = best_target(&targets, 600.0f64).unwrap();
+        assert!(core::ptr::eq(best, &targets[1]));
+        assert_eq!(Some(solution), linear_aim(&targets[1], 600.0));
+        assert!(solution.time < linear_aim(&targets[2], 600.0).unwrap().time);
+        assert!(best_target(&targets[..1], 600.0).is_none());
+    }
+}
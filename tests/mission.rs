@@ -0,0 +1,42 @@
+//! Search, acquisition, homing, and fuzing working together
+
+use guidance::{
+    step_engagement, Acquisition, AcquisitionState, Body, EngagementState, Ipn, ProximityFuze,
+    SearchAndHome, WeaveSearch,
+};
+
+#[test]
+fn nominal_mission() {
+    const TIMESTEP: f64 = 1e-3;
+    let mut state = EngagementState::new(
+        Body {
+            position: na::Point3::origin(),
+            velocity: na::Vector3::new(0.0, 600.0, 0.0),
+        },
+        Body {
+            position: na::Point3::new(3000.0, 14000.0, 500.0),
+            velocity: na::Vector3::new(-200.0, 0.0, 0.0),
+        },
+        300.0,
+    );
+    let mut law = SearchAndHome {
+        acquisition: Acquisition::new(10_000.0, 0.6, 0.5),
+        search: WeaveSearch::new(50.0, 4.0, na::Vector3::z_axis()),
+        law: Ipn {
+            navigation_constant: 4.0,
+        },
+    };
+    let fuze = ProximityFuze { radius: 5.0 };
+    let mut acquired = false;
+    let mut detonation = None;
+    while state.time < 60.0 {
+        if let Some(miss) = fuze.check(&state.relative(), TIMESTEP) {
+            detonation = Some(miss);
+            break;
+        }
+        step_engagement(&mut state, &mut law, TIMESTEP);
+        acquired |= law.acquisition.state() == AcquisitionState::Locked;
+    }
+    assert!(acquired);
+    assert!(detonation.unwrap() < 5.0);
+}
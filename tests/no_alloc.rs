@@ -0,0 +1,48 @@
+//! Confirms that stepping an engagement never touches the heap
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use guidance::{step_engagement, Body, EngagementState, Ipn, StepOutcome};
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn engagement_is_allocation_free() {
+    let mut state = EngagementState::new(
+        Body {
+            position: na::Point3::origin(),
+            velocity: na::Vector3::new(0.0, 800.0, 0.0),
+        },
+        Body {
+            position: na::Point3::new(200.0, 5000.0, 100.0),
+            velocity: na::Vector3::new(0.0, -300.0, 0.0),
+        },
+        300.0,
+    );
+    let mut law = Ipn {
+        navigation_constant: 3.0,
+    };
+    let before = ALLOCATIONS.with(Cell::get);
+    while step_engagement(&mut state, &mut law, 1e-3) == StepOutcome::Closing {}
+    assert_eq!(ALLOCATIONS.with(Cell::get), before);
+    assert!(state.range() < 1.0);
+}